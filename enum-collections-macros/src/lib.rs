@@ -1,58 +1,272 @@
 use proc_macro::TokenStream;
 use quote::{quote, quote_spanned};
-use syn::{DeriveInput, parse_macro_input, spanned::Spanned};
+use syn::{DataEnum, DataStruct, DeriveInput, parse_macro_input, spanned::Spanned};
 
 /// Creates `enum_map::Enumerated` implementation for the underlying Enum.
 /// Also derives Copy and Clone.
-#[proc_macro_derive(Enumerated)]
+///
+/// Enums annotated with `#[enumerated(const_items)]` also get an inherent `const fn const_position(&self) -> usize`
+/// and a `const ALL: [Self; SIZE]` array of their variants, both usable in const context, e.g. with
+/// `EnumMap::get_by_position`. They're opt-in, enums may define inherent items of the same names themselves.
+///
+/// Variants gated by `#[cfg(...)]` are left out of `SIZE`, `VARIANTS` and the other tables when configured out.
+/// Positions of the following variants shift accordingly.
+///
+/// The generated code refers to helpers of the `enum_collections` crate by their absolute path, the crate must not
+/// be renamed in `Cargo.toml`.
+///
+/// Structs are supported as well, if all of their fields are `Enumerated` and `Copy`.
+/// Such a struct has a variant for each combination of its fields' variants. Variants are positioned
+/// in lexicographic order, the first field being the most significant one. This is the same order
+/// `#[derive(PartialOrd, Ord)]` uses.
+#[proc_macro_derive(Enumerated, attributes(enumerated))]
 pub fn derive_enum_collections(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    match &input.data {
+        syn::Data::Enum(en) => derive_enum(&input, en),
+        syn::Data::Struct(st) => derive_struct(&input, st),
+        syn::Data::Union(_) => quote_spanned! {
+            input.span() => compile_error!("The `Enumerated` macro only supports enums and structs.");
+        }
+        .into(),
+    }
+}
+
+/// Whether `#[enumerated(const_items)]` is present. Any other option is an error.
+fn const_items(input: &DeriveInput) -> syn::Result<bool> {
+    let mut const_items = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("enumerated"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("const_items") {
+                const_items = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `enumerated` option, expected `const_items`"))
+            }
+        })?;
+    }
+    Ok(const_items)
+}
+
+fn derive_enum(input: &DeriveInput, en: &DataEnum) -> TokenStream {
     let generics = &input.generics;
     let name = &input.ident;
-    let syn::Data::Enum(en) = input.data else {
-        return quote_spanned! {
-            input.span() => compile_error!("The `Enumerated` macro only supports enums.");
-        }
-        .into();
+    let const_items = match const_items(input) {
+        Ok(const_items) => const_items,
+        Err(error) => return error.to_compile_error().into(),
     };
 
-    let enum_len = en.variants.len();
     let mut variants = proc_macro2::TokenStream::new();
-    for variant in en.variants {
-        if let Some((_, discriminant)) = variant.discriminant {
+    let mut variant_names = proc_macro2::TokenStream::new();
+    let mut position_arms = proc_macro2::TokenStream::new();
+    let mut from_position_arms = proc_macro2::TokenStream::new();
+    let mut position_consts = proc_macro2::TokenStream::new();
+    for (index, variant) in en.variants.iter().enumerate() {
+        if let Some((_, discriminant)) = &variant.discriminant {
             return quote_spanned! {
                 discriminant.span() => compile_error!("`Enumerated` doesn't support discriminants");
             }
             .into();
         }
-        let variant_name = variant.ident;
-        variants.extend(quote! { Self::#variant_name, });
+        // Variants configured out are left out of every table, positions of the others shift with them
+        let cfgs: Vec<&syn::Attribute> = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .collect();
+        let variant_name = &variant.ident;
+        let variant_name_str = variant_name.to_string();
+        variants.extend(quote! { #(#cfgs)* Self::#variant_name, });
+        variant_names.extend(quote! { #(#cfgs)* #variant_name_str, });
+        position_arms.extend(quote! {
+            #(#cfgs)* Self::#variant_name => Self::#variant_name as usize,
+        });
+        // Positions are known to the compiler only, cfg-gated variants shift them. Named consts let
+        // `from_position` match on them directly, compiled into a jump table rather than a chain of comparisons.
+        let position_const = quote::format_ident!("POSITION_{}", index);
+        position_consts.extend(quote! {
+            #(#cfgs)* const #position_const: usize = #name::#variant_name as usize;
+        });
+        from_position_arms.extend(quote! {
+            #(#cfgs)* #position_const => Self::#variant_name,
+        });
     }
+    let name_str = name.to_string();
 
     #[cfg(feature = "variants")]
-    return quote! {
+    let variants_const = quote! {
+        const VARIANTS: &'static [Self] = &[#variants];
+    };
+    #[cfg(not(feature = "variants"))]
+    let variants_const = proc_macro2::TokenStream::new();
+
+    // Usable in const context, unlike the trait's methods
+    let const_items = if const_items {
+        quote! {
+            #[allow(dead_code)]
+            impl #generics #name #generics {
+                /// Position of the variant, the same as `Enumerated::position`.
+                pub const fn const_position(&self) -> usize {
+                    match *self {
+                        #position_arms
+                    }
+                }
+
+                /// All variants, in the order of their positions.
+                pub const ALL: [Self; <Self as Enumerated>::SIZE] = [#variants];
+            }
+        }
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+
+    quote! {
+        #const_items
+
         impl #generics Enumerated for #name #generics {
 
-            fn position(self) -> usize {
-                self as usize
+            fn position(&self) -> usize {
+                // Unit variants are matched without moving out of the reference, the enum doesn't need to be `Copy`
+                match *self {
+                    #position_arms
+                }
+            }
+
+            fn from_position(position: usize) -> Self {
+                #position_consts
+                match position {
+                    #from_position_arms
+                    _ => panic!("Position {} is out of range of `{}`", position, #name_str),
+                }
             }
 
-            const SIZE: usize = #enum_len;
-            const VARIANTS: &'static [Self] = &[#variants];
+            const SIZE: usize = Self::VARIANT_NAMES.len();
+            const VARIANT_NAMES: &'static [&'static str] = &[#variant_names];
+            // Hashes names of the variants configured in only, the same function hand-written implementations use
+            const LAYOUT_HASH: u64 = ::enum_collections::__layout_hash(Self::VARIANT_NAMES);
+            #variants_const
         }
     }
-    .into();
+    .into()
+}
 
+fn derive_struct(input: &DeriveInput, st: &DataStruct) -> TokenStream {
+    let name = &input.ident;
+    match const_items(input) {
+        Ok(false) => {}
+        Ok(true) => {
+            return quote_spanned! {
+                input.span() => compile_error!("`#[enumerated(const_items)]` is supported on enums only");
+            }
+            .into();
+        }
+        Err(error) => return error.to_compile_error().into(),
+    }
+    if !input.generics.params.is_empty() {
+        return quote_spanned! {
+            input.generics.span() => compile_error!("`Enumerated` doesn't support generic structs");
+        }
+        .into();
+    }
+
+    // Tuple struct fields are accessed by their index
+    let members: Vec<syn::Member> = st
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        })
+        .collect();
+    let types: Vec<&syn::Type> = st.fields.iter().map(|field| &field.ty).collect();
+
+    let position = quote! {
+        let mut position = 0;
+        #(position = position * <#types as Enumerated>::SIZE + Enumerated::position(&self.#members);)*
+        position
+    };
+
+    let bindings: Vec<syn::Ident> = (0..members.len())
+        .map(|index| quote::format_ident!("field_{}", index))
+        .collect();
+    // The last field is the least significant one, fields are therefore resolved in reverse.
+    let reversed_bindings: Vec<&syn::Ident> = bindings.iter().rev().collect();
+    let reversed_types: Vec<&&syn::Type> = types.iter().rev().collect();
+    let construction = match &st.fields {
+        syn::Fields::Named(_) => quote! { #name { #(#members: #bindings),* } },
+        syn::Fields::Unnamed(_) => quote! { #name ( #(#bindings),* ) },
+        syn::Fields::Unit => quote! { #name },
+    };
+    let name_str = name.to_string();
+    // Field names are hashed the same way variant names are, then combined with the fields' own layouts
+    let field_names: Vec<String> = members
+        .iter()
+        .map(|member| match member {
+            syn::Member::Named(ident) => ident.to_string(),
+            syn::Member::Unnamed(index) => index.index.to_string(),
+        })
+        .collect();
+
+    #[cfg(feature = "variants")]
+    let variants_const = quote! {
+        const VARIANTS: &'static [Self] = {
+            #[allow(unused_mut, unused_variables)]
+            const fn variant(mut position: usize) -> #name {
+                #(
+                    let #reversed_bindings = <#reversed_types as Enumerated>::VARIANTS[position % <#reversed_types as Enumerated>::SIZE];
+                    position /= <#reversed_types as Enumerated>::SIZE;
+                )*
+                #construction
+            }
+
+            const VARIANTS: [#name; <#name as Enumerated>::SIZE] = {
+                const FIRST: #name = variant(0);
+                let mut variants = [FIRST; <#name as Enumerated>::SIZE];
+                let mut position = 1;
+                while position < variants.len() {
+                    variants[position] = variant(position);
+                    position += 1;
+                }
+                variants
+            };
+            &VARIANTS
+        };
+    };
     #[cfg(not(feature = "variants"))]
-    return quote! {
-        impl #generics Enumerated for #name #generics {
+    let variants_const = proc_macro2::TokenStream::new();
+
+    quote! {
+        impl Enumerated for #name {
+
+            fn position(&self) -> usize {
+                #position
+            }
 
-            fn position(self) -> usize {
-                self as usize
+            #[allow(unused_mut)]
+            fn from_position(mut position: usize) -> Self {
+                if position >= <Self as Enumerated>::SIZE {
+                    panic!("Position {} is out of range of `{}`", position, #name_str);
+                }
+                #(
+                    let #reversed_bindings = <#reversed_types as Enumerated>::from_position(position % <#reversed_types as Enumerated>::SIZE);
+                    position /= <#reversed_types as Enumerated>::SIZE;
+                )*
+                #construction
             }
 
-            const SIZE: usize = #enum_len;
+            const SIZE: usize = 1 #(* <#types as Enumerated>::SIZE)*;
+            const LAYOUT_HASH: u64 = {
+                let hash = ::enum_collections::__layout_hash(&[#(#field_names),*]);
+                #(let hash = ::enum_collections::__combine_layout_hash(hash, <#types as Enumerated>::LAYOUT_HASH);)*
+                hash
+            };
+            #variants_const
         }
     }
-    .into();
+    .into()
 }
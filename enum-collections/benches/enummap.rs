@@ -1,7 +1,7 @@
 use std::{collections::HashMap, hash::Hash};
 
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use enum_collections::{EnumMap, Enumerated};
+use enum_collections::{EnumMap, Enumerated, SparseEnumMap};
 
 #[derive(Enumerated, Eq, PartialEq, Hash)] // Enum derived to benchmark against the `enum-map` crate
 #[allow(dead_code)]
@@ -33,19 +33,19 @@ fn enummap_insert(criterion: &mut Criterion) {
 
 fn enummap_new_default(criterion: &mut Criterion) {
     criterion.bench_function("EnumMap new: default", |bencher| {
-        bencher.iter(|| EnumMap::<Letter, i32, { Letter::SIZE }>::new_default())
+        bencher.iter(EnumMap::<Letter, i32, { Letter::SIZE }>::new_default)
     });
 }
 
 fn enummap_new_option(criterion: &mut Criterion) {
     criterion.bench_function("EnumMap new: Option::None", |bencher| {
-        bencher.iter(|| EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option())
+        bencher.iter(EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option)
     });
 }
 
 fn enummap_new(criterion: &mut Criterion) {
     criterion.bench_function("EnumMap new: provider fn", |bencher| {
-        bencher.iter(|| (EnumMap::<Letter, i32, { Letter::SIZE }>::new(|| 42))) // Tested without a black box, expected to be optimized in real-world usage
+        bencher.iter(|| EnumMap::<Letter, i32, { Letter::SIZE }>::new(|| 42)) // Tested without a black box, expected to be optimized in real-world usage
     });
 }
 
@@ -60,6 +60,96 @@ fn enummap_new_inspect(criterion: &mut Criterion) {
     });
 }
 
+#[derive(Enumerated, Clone, Copy)]
+#[allow(dead_code)]
+enum Digit {
+    D0,
+    D1,
+    D2,
+    D3,
+    D4,
+    D5,
+    D6,
+    D7,
+    D8,
+    D9,
+}
+
+/// A thousand variants, a handful of them populated, to compare sparse and dense maps
+#[derive(Enumerated, Clone, Copy)]
+struct Opcode(Digit, Digit, Digit);
+
+const POPULATED: [Opcode; 4] = [
+    Opcode(Digit::D0, Digit::D0, Digit::D1),
+    Opcode(Digit::D1, Digit::D2, Digit::D8),
+    Opcode(Digit::D5, Digit::D0, Digit::D0),
+    Opcode(Digit::D9, Digit::D9, Digit::D9),
+];
+
+fn sparse_get(criterion: &mut Criterion) {
+    let sparse_map: SparseEnumMap<Opcode, i32> =
+        POPULATED.iter().map(|opcode| (*opcode, 1)).collect();
+    let dense_map: EnumMap<Opcode, Option<i32>, { Opcode::SIZE }> = sparse_map.clone().into();
+    let mut group = criterion.benchmark_group("1000 variants, 4 populated: get");
+    group.bench_function("SparseEnumMap", |bencher| {
+        bencher.iter(|| sparse_map.get(black_box(&POPULATED[3])).copied())
+    });
+    group.bench_function("EnumMap<Option>", |bencher| {
+        bencher.iter(|| dense_map[black_box(&POPULATED[3])])
+    });
+    group.finish();
+}
+
+fn sparse_insert(criterion: &mut Criterion) {
+    let mut sparse_map: SparseEnumMap<Opcode, i32> =
+        POPULATED.iter().map(|opcode| (*opcode, 1)).collect();
+    let mut dense_map: EnumMap<Opcode, Option<i32>, { Opcode::SIZE }> = sparse_map.clone().into();
+    let mut group = criterion.benchmark_group("1000 variants, 4 populated: insert");
+    group.bench_function("SparseEnumMap", |bencher| {
+        bencher.iter(|| sparse_map.insert(black_box(POPULATED[2]), black_box(42)))
+    });
+    group.bench_function("EnumMap<Option>", |bencher| {
+        bencher.iter(|| dense_map[black_box(POPULATED[2])] = black_box(Some(42)))
+    });
+    group.finish();
+}
+
+fn sparse_new(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("1000 variants, 4 populated: new");
+    group.bench_function("SparseEnumMap", |bencher| {
+        bencher.iter(|| {
+            POPULATED
+                .iter()
+                .map(|opcode| (*opcode, 1))
+                .collect::<SparseEnumMap<Opcode, i32>>()
+        })
+    });
+    group.bench_function("EnumMap<Option>", |bencher| {
+        bencher.iter(|| {
+            let mut dense_map = EnumMap::<Opcode, Option<i32>, { Opcode::SIZE }>::new_option();
+            for opcode in POPULATED {
+                dense_map[opcode] = Some(1);
+            }
+            dense_map
+        })
+    });
+    group.finish();
+}
+
+fn sparse_iter(criterion: &mut Criterion) {
+    let sparse_map: SparseEnumMap<Opcode, i32> =
+        POPULATED.iter().map(|opcode| (*opcode, 1)).collect();
+    let dense_map: EnumMap<Opcode, Option<i32>, { Opcode::SIZE }> = sparse_map.clone().into();
+    let mut group = criterion.benchmark_group("1000 variants, 4 populated: sum of values");
+    group.bench_function("SparseEnumMap", |bencher| {
+        bencher.iter(|| black_box(&sparse_map).iter().sum::<i32>())
+    });
+    group.bench_function("EnumMap<Option>", |bencher| {
+        bencher.iter(|| black_box(&dense_map).iter().flatten().sum::<i32>())
+    });
+    group.finish();
+}
+
 fn std_hashmap_get(criterion: &mut Criterion) {
     let mut hashmap: HashMap<Letter, i32> = HashMap::new();
     hashmap.insert(Letter::A, 1);
@@ -83,6 +173,10 @@ criterion_group!(
     enummap_new_option,
     enummap_new,
     enummap_new_inspect,
+    sparse_get,
+    sparse_insert,
+    sparse_new,
+    sparse_iter,
     std_hashmap_get,
     std_hashmap_insert,
 );
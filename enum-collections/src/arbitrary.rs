@@ -0,0 +1,98 @@
+//! Fuzzing support using the `arbitrary` crate.
+//!
+//! Each value of an [EnumMap] is generated independently, so fuzzers mutate and minimize the map entry by entry.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use std::marker::PhantomData;
+
+use crate::{EnumMap, Enumerated};
+
+impl<'a, K: Enumerated, V: Arbitrary<'a>, const N: usize> Arbitrary<'a> for EnumMap<K, V, N> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            data: <[V; N]>::arbitrary(u)?,
+            _key: PhantomData,
+        })
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            data: <[V; N]>::arbitrary_take_rest(u)?,
+            _key: PhantomData,
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[V; N]>::size_hint(depth)
+    }
+}
+
+/// Generates an arbitrary variant of an [Enumerated] type.
+///
+/// `Arbitrary` can't be implemented for every [Enumerated] type by this crate, use this function
+/// in a manual `Arbitrary` implementation or with `#[arbitrary(with = arbitrary_variant)]`.
+///
+/// Returns an error if `K` has no variants.
+///
+/// ```
+/// use arbitrary::Unstructured;
+/// use enum_collections::{Enumerated, arbitrary_variant};
+///
+/// #[derive(Enumerated, Debug, PartialEq)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut u = Unstructured::new(&[1, 0, 0, 0]);
+/// assert_eq!(Letter::B, arbitrary_variant::<Letter>(&mut u).unwrap());
+/// ```
+pub fn arbitrary_variant<K: Enumerated>(u: &mut Unstructured<'_>) -> Result<K> {
+    Ok(K::from_position(u.choose_index(K::SIZE)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::{EnumMap, Enumerated, arbitrary_variant};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Setting {
+        Volume,
+        Brightness,
+        Contrast,
+    }
+
+    #[test]
+    fn enum_map() {
+        let bytes = [1, 2, 3];
+        let mut u = Unstructured::new(&bytes);
+        let enum_map = EnumMap::<Setting, u8, { Setting::SIZE }>::arbitrary(&mut u).unwrap();
+        assert_eq!(1, enum_map[Setting::Volume]);
+        assert_eq!(2, enum_map[Setting::Brightness]);
+        assert_eq!(3, enum_map[Setting::Contrast]);
+        assert_eq!(
+            (3, Some(3)),
+            EnumMap::<Setting, u8, { Setting::SIZE }>::size_hint(0)
+        );
+    }
+
+    #[test]
+    fn variants() {
+        for (byte, setting) in [
+            (0, Setting::Volume),
+            (1, Setting::Brightness),
+            (2, Setting::Contrast),
+        ] {
+            let bytes = [byte, 0, 0, 0];
+            let mut u = Unstructured::new(&bytes);
+            assert_eq!(setting, arbitrary_variant::<Setting>(&mut u).unwrap());
+        }
+
+        #[derive(Enumerated, Debug)]
+        enum Empty {}
+        let mut u = Unstructured::new(&[0, 0, 0, 0]);
+        assert!(arbitrary_variant::<Empty>(&mut u).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Positional binary encoding of [EnumMap] using `bincode` 2.
+//!
+//! The number of values and the key's [Enumerated::LAYOUT_HASH] are written first, followed by the values
+//! in the order of their variants' positions. Keys themselves are never written. Decoding fails if the number
+//! of values or the layout of the key doesn't match the map, e.g. after the key's variants were reordered.
+
+use bincode::{
+    BorrowDecode, Decode, Encode,
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+};
+use std::marker::PhantomData;
+
+use crate::{EnumMap, Enumerated};
+
+/// Encodes the number of values and the key's layout hash, followed by the values in the order of their variants' positions.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let enum_map = EnumMap::<Letter, u8, { Letter::SIZE }>::new_inspect(|letter| match letter {
+///     Letter::A => 42,
+///     Letter::B => 24,
+/// });
+/// let bytes = bincode::encode_to_vec(&enum_map, bincode::config::standard()).unwrap();
+/// assert_eq!(2, bytes[0]);
+/// assert_eq!(&[42, 24], &bytes[bytes.len() - 2..]);
+/// ```
+impl<K: Enumerated, V: Encode, const N: usize> Encode for EnumMap<K, V, N> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        N.encode(encoder)?;
+        K::LAYOUT_HASH.encode(encoder)?;
+        self.data.encode(encoder)
+    }
+}
+
+/// Decodes the values in the order of their variants' positions.
+/// Fails with [DecodeError::ArrayLengthMismatch] if the encoded number of values doesn't match the map,
+/// and with [DecodeError::OtherString] if the key's layout hash differs.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// #[derive(Enumerated)]
+/// pub enum Reordered {
+///    B,
+///    A,
+/// }
+///
+/// let enum_map = EnumMap::<Letter, u8, { Letter::SIZE }>::new(|| 42);
+/// let bytes = bincode::encode_to_vec(&enum_map, bincode::config::standard()).unwrap();
+/// let (decoded, _): (EnumMap<Letter, u8, { Letter::SIZE }>, usize) =
+///     bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+/// assert_eq!(42, decoded[Letter::B]);
+///
+/// let mismatched: Result<(EnumMap<Reordered, u8, { Reordered::SIZE }>, usize), _> =
+///     bincode::decode_from_slice(&bytes, bincode::config::standard());
+/// assert!(mismatched.is_err());
+/// ```
+impl<Context, K: Enumerated, V: Decode<Context>, const N: usize> Decode<Context>
+    for EnumMap<K, V, N>
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decode_header::<K, N>(usize::decode(decoder)?, u64::decode(decoder)?)?;
+        Ok(Self {
+            data: <[V; N]>::decode(decoder)?,
+            _key: PhantomData,
+        })
+    }
+}
+
+impl<'de, Context, K: Enumerated, V: BorrowDecode<'de, Context>, const N: usize>
+    BorrowDecode<'de, Context> for EnumMap<K, V, N>
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        decode_header::<K, N>(usize::decode(decoder)?, u64::decode(decoder)?)?;
+        Ok(Self {
+            data: <[V; N]>::borrow_decode(decoder)?,
+            _key: PhantomData,
+        })
+    }
+}
+
+/// Rejects an encoded number of values or a layout hash different from the map's.
+fn decode_header<K: Enumerated, const N: usize>(
+    length: usize,
+    layout_hash: u64,
+) -> Result<(), DecodeError> {
+    if length != N {
+        return Err(DecodeError::ArrayLengthMismatch {
+            required: N,
+            found: length,
+        });
+    }
+    if layout_hash != K::LAYOUT_HASH {
+        return Err(DecodeError::OtherString(format!(
+            "EnumMap was encoded with a different layout of `{}`, its variants have changed",
+            std::any::type_name::<K>()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bincode::{config, error::DecodeError};
+
+    use crate::{EnumMap, Enumerated};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Region {
+        Europe,
+        Asia,
+        Americas,
+    }
+
+    #[test]
+    fn round_trip() {
+        let enum_map = EnumMap::<Region, String, { Region::SIZE }>::new_inspect(|region| {
+            format!("{:?}", region)
+        });
+        let bytes = bincode::encode_to_vec(&enum_map, config::standard()).unwrap();
+        let (decoded, read): (EnumMap<Region, String, { Region::SIZE }>, usize) =
+            bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+        assert_eq!(enum_map, decoded);
+        assert_eq!(bytes.len(), read);
+
+        let (borrowed, _): (EnumMap<Region, &str, { Region::SIZE }>, usize) =
+            bincode::borrow_decode_from_slice(&bytes, config::standard()).unwrap();
+        assert_eq!("Asia", borrowed[Region::Asia]);
+    }
+
+    #[test]
+    fn mismatched_count() {
+        // Encoded with an older version of the key, which had two variants only
+        let bytes = bincode::encode_to_vec(
+            (2usize, Region::LAYOUT_HASH, 7u32, 8u32),
+            config::standard(),
+        )
+        .unwrap();
+        let result: Result<(EnumMap<Region, u32, { Region::SIZE }>, usize), _> =
+            bincode::decode_from_slice(&bytes, config::standard());
+        assert!(matches!(
+            result,
+            Err(DecodeError::ArrayLengthMismatch {
+                required: 3,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn mismatched_layout() {
+        #[derive(Enumerated)]
+        enum Renamed {
+            Europe,
+            Asia,
+            America,
+        }
+
+        let enum_map = EnumMap::<Region, u32, { Region::SIZE }>::new_default();
+        let bytes = bincode::encode_to_vec(enum_map, config::standard()).unwrap();
+        let result: Result<(EnumMap<Renamed, u32, { Renamed::SIZE }>, usize), _> =
+            bincode::decode_from_slice(&bytes, config::standard());
+        assert!(matches!(result, Err(DecodeError::OtherString(_))));
+    }
+}
@@ -0,0 +1,93 @@
+//! Set of positions, one bit per variant.
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Presence of values at positions `0..size`, a bit per position.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct Bitmap {
+    words: Box<[u64]>,
+}
+
+impl Bitmap {
+    /// Bitmap of `size` positions, none of them present.
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            words: vec![0; size.div_ceil(WORD_BITS)].into_boxed_slice(),
+        }
+    }
+
+    pub(crate) fn contains(&self, position: usize) -> bool {
+        self.words[position / WORD_BITS] & (1 << (position % WORD_BITS)) != 0
+    }
+
+    /// Marks the position present. Returns `true` if it was absent.
+    pub(crate) fn insert(&mut self, position: usize) -> bool {
+        let absent = !self.contains(position);
+        self.words[position / WORD_BITS] |= 1 << (position % WORD_BITS);
+        absent
+    }
+
+    /// Marks the position absent. Returns `true` if it was present.
+    pub(crate) fn remove(&mut self, position: usize) -> bool {
+        let present = self.contains(position);
+        self.words[position / WORD_BITS] &= !(1 << (position % WORD_BITS));
+        present
+    }
+
+    /// Number of present positions lower than `position`.
+    pub(crate) fn rank(&self, position: usize) -> usize {
+        let word = position / WORD_BITS;
+        let below = self.words[..word]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum::<usize>();
+        let mask = (1u64 << (position % WORD_BITS)) - 1;
+        below + (self.words[word] & mask).count_ones() as usize
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Present positions, in ascending order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(index * WORD_BITS + bit)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bitmap;
+
+    #[test]
+    fn insert_remove_rank() {
+        let mut bitmap = Bitmap::new(200);
+        assert!(bitmap.insert(3));
+        assert!(!bitmap.insert(3));
+        assert!(bitmap.insert(64));
+        assert!(bitmap.insert(199));
+        assert!(bitmap.contains(64));
+        assert!(!bitmap.contains(65));
+
+        assert_eq!(0, bitmap.rank(3));
+        assert_eq!(1, bitmap.rank(64));
+        assert_eq!(2, bitmap.rank(199));
+        assert_eq!(vec![3, 64, 199], bitmap.iter().collect::<Vec<_>>());
+
+        assert!(bitmap.remove(64));
+        assert!(!bitmap.remove(64));
+        assert_eq!(1, bitmap.rank(199));
+        bitmap.clear();
+        assert_eq!(None, bitmap.iter().next());
+    }
+}
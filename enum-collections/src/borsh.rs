@@ -0,0 +1,147 @@
+//! Positional binary encoding of [EnumMap] using `borsh`.
+//!
+//! Like Borsh's own collections, the number of values is written first as a `u32`. The key's
+//! [Enumerated::LAYOUT_HASH] follows as a `u64`, then the values in the order of their variants' positions.
+//! Keys themselves are never written. Deserialization fails if the number of values or the layout
+//! of the key doesn't match the map, e.g. after the key's variants were reordered.
+
+use borsh::{
+    BorshDeserialize, BorshSerialize,
+    io::{Error, ErrorKind, Read, Result, Write},
+};
+use std::marker::PhantomData;
+
+use crate::{EnumMap, Enumerated};
+
+/// Serializes the number of values as a `u32` and the key's layout hash as a `u64`,
+/// followed by the values in the order of their variants' positions.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let enum_map = EnumMap::<Letter, u8, { Letter::SIZE }>::new_inspect(|letter| match letter {
+///     Letter::A => 42,
+///     Letter::B => 24,
+/// });
+/// let bytes = borsh::to_vec(&enum_map).unwrap();
+/// assert_eq!(&[2, 0, 0, 0], &bytes[..4]);
+/// assert_eq!(&Letter::LAYOUT_HASH.to_le_bytes(), &bytes[4..12]);
+/// assert_eq!(&[42, 24], &bytes[12..]);
+/// ```
+impl<K: Enumerated, V: BorshSerialize, const N: usize> BorshSerialize for EnumMap<K, V, N> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let length = u32::try_from(N).map_err(|_| Error::from(ErrorKind::InvalidData))?;
+        length.serialize(writer)?;
+        K::LAYOUT_HASH.serialize(writer)?;
+        self.data.serialize(writer)
+    }
+}
+
+/// Deserializes the values in the order of their variants' positions.
+/// Fails with [ErrorKind::InvalidData] if the serialized number of values or the key's layout hash
+/// doesn't match the map.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// #[derive(Enumerated)]
+/// pub enum Reordered {
+///    B,
+///    A,
+/// }
+///
+/// let bytes = borsh::to_vec(&EnumMap::<Letter, u8, { Letter::SIZE }>::new(|| 42)).unwrap();
+/// let enum_map: EnumMap<Letter, u8, { Letter::SIZE }> = borsh::from_slice(&bytes).unwrap();
+/// assert_eq!(42, enum_map[Letter::B]);
+///
+/// let mismatched = borsh::from_slice::<EnumMap<Reordered, u8, { Reordered::SIZE }>>(&bytes);
+/// assert!(mismatched.is_err());
+/// ```
+impl<K: Enumerated, V: BorshDeserialize, const N: usize> BorshDeserialize for EnumMap<K, V, N> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let length = u32::deserialize_reader(reader)?;
+        if usize::try_from(length).ok() != Some(N) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Expected {} values in EnumMap, found {}", N, length),
+            ));
+        }
+        if u64::deserialize_reader(reader)? != K::LAYOUT_HASH {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "EnumMap was serialized with a different layout of `{}`, its variants have changed",
+                    std::any::type_name::<K>()
+                ),
+            ));
+        }
+        Ok(Self {
+            data: <[V; N]>::deserialize_reader(reader)?,
+            _key: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::io::ErrorKind;
+
+    use crate::{EnumMap, Enumerated};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Region {
+        Europe,
+        Asia,
+        Americas,
+    }
+
+    #[test]
+    fn round_trip() {
+        let enum_map = EnumMap::<Region, String, { Region::SIZE }>::new_inspect(|region| {
+            format!("{:?}", region)
+        });
+        let bytes = borsh::to_vec(&enum_map).unwrap();
+        let deserialized: EnumMap<Region, String, { Region::SIZE }> =
+            borsh::from_slice(&bytes).unwrap();
+        assert_eq!(enum_map, deserialized);
+    }
+
+    #[test]
+    fn mismatched_count() {
+        // Serialized with an older version of the key, which had two variants only
+        let bytes = borsh::to_vec(&(2u32, Region::LAYOUT_HASH, 7u32, 8u32)).unwrap();
+        let error = borsh::from_slice::<EnumMap<Region, u32, { Region::SIZE }>>(&bytes)
+            .err()
+            .unwrap();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+        assert_eq!("Expected 3 values in EnumMap, found 2", error.to_string());
+    }
+
+    #[test]
+    fn mismatched_layout() {
+        #[derive(Enumerated)]
+        enum Reordered {
+            Asia,
+            Europe,
+            Americas,
+        }
+
+        let bytes =
+            borsh::to_vec(&EnumMap::<Region, u32, { Region::SIZE }>::new_default()).unwrap();
+        let error = borsh::from_slice::<EnumMap<Reordered, u32, { Reordered::SIZE }>>(&bytes)
+            .err()
+            .unwrap();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+        assert!(error.to_string().contains("different layout"));
+    }
+}
@@ -0,0 +1,322 @@
+//! Integers within an inclusive range, usable as keys of an EnumMap.
+
+use std::fmt::Display;
+
+use crate::{
+    Enumerated,
+    enumerated::{combine_layout_hash, layout_hash},
+};
+
+/// Largest range providing `Enumerated::VARIANTS`, the slice being cut from a table of this length.
+#[cfg(feature = "variants")]
+const MAX_VARIANTS: usize = 256;
+
+/// Integer within the inclusive range `LO..=HI`, positioned in ascending order, `LO` being the first one.
+///
+/// Tables indexed by small integer ranges, e.g. hours, priorities or channels, get the type safety of enum keys.
+/// Values out of the range can't be constructed, arithmetic either saturates at the bounds or fails.
+/// With the `variants` feature, ranges of up to 256 values provide `VARIANTS`.
+///
+/// ```
+/// use enum_collections::{Bounded, EnumMap, Enumerated};
+///
+/// type Hour = Bounded<0, 23>;
+///
+/// let mut temperatures = EnumMap::<Hour, f64, { Hour::SIZE }>::new_default();
+/// let noon = Hour::new(12).unwrap();
+/// temperatures[noon] = 21.5;
+/// assert_eq!(21.5, temperatures[noon]);
+/// assert_eq!(24, Hour::SIZE);
+///
+/// assert_eq!(None, Hour::new(24));
+/// assert_eq!(Hour::MAX, noon.saturating_add(100));
+/// assert_eq!(None, noon.checked_add(12));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Bounded<const LO: i64, const HI: i64>(i64);
+
+impl<const LO: i64, const HI: i64> Bounded<LO, HI> {
+    /// The lowest value, `LO`.
+    pub const MIN: Self = Self(LO);
+    /// The highest value, `HI`.
+    pub const MAX: Self = Self(HI);
+
+    /// The value, if within `LO..=HI`.
+    ///
+    /// ```
+    /// use enum_collections::Bounded;
+    ///
+    /// assert_eq!(Some(16), Bounded::<1, 16>::new(16).map(Bounded::get));
+    /// assert_eq!(None, Bounded::<1, 16>::new(0));
+    /// ```
+    pub const fn new(value: i64) -> Option<Self> {
+        if LO <= value && value <= HI {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    /// The value as a plain integer.
+    ///
+    /// ```
+    /// use enum_collections::Bounded;
+    ///
+    /// assert_eq!(7, Bounded::<0, 7>::MAX.get());
+    /// ```
+    pub const fn get(self) -> i64 {
+        self.0
+    }
+
+    /// Adds to the value, `None` if the sum is out of the range.
+    ///
+    /// ```
+    /// use enum_collections::Bounded;
+    ///
+    /// let priority = Bounded::<0, 7>::new(5).unwrap();
+    /// assert_eq!(Bounded::new(7), priority.checked_add(2));
+    /// assert_eq!(None, priority.checked_add(3));
+    /// ```
+    pub const fn checked_add(self, rhs: i64) -> Option<Self> {
+        match self.0.checked_add(rhs) {
+            Some(value) => Self::new(value),
+            None => None,
+        }
+    }
+
+    /// Subtracts from the value, `None` if the difference is out of the range.
+    ///
+    /// ```
+    /// use enum_collections::Bounded;
+    ///
+    /// let priority = Bounded::<0, 7>::new(2).unwrap();
+    /// assert_eq!(Bounded::new(0), priority.checked_sub(2));
+    /// assert_eq!(None, priority.checked_sub(3));
+    /// ```
+    pub const fn checked_sub(self, rhs: i64) -> Option<Self> {
+        match self.0.checked_sub(rhs) {
+            Some(value) => Self::new(value),
+            None => None,
+        }
+    }
+
+    /// Adds to the value, stopping at the bounds of the range.
+    ///
+    /// ```
+    /// use enum_collections::Bounded;
+    ///
+    /// let priority = Bounded::<0, 7>::new(5).unwrap();
+    /// assert_eq!(Bounded::<0, 7>::MAX, priority.saturating_add(10));
+    /// assert_eq!(Bounded::<0, 7>::MIN, priority.saturating_add(-10));
+    /// ```
+    pub const fn saturating_add(self, rhs: i64) -> Self {
+        Self::clamp(self.0.saturating_add(rhs))
+    }
+
+    /// Subtracts from the value, stopping at the bounds of the range.
+    ///
+    /// ```
+    /// use enum_collections::Bounded;
+    ///
+    /// let priority = Bounded::<0, 7>::new(2).unwrap();
+    /// assert_eq!(Bounded::<0, 7>::MIN, priority.saturating_sub(10));
+    /// ```
+    pub const fn saturating_sub(self, rhs: i64) -> Self {
+        Self::clamp(self.0.saturating_sub(rhs))
+    }
+
+    /// The value closest to the given one within the range.
+    ///
+    /// ```
+    /// use enum_collections::Bounded;
+    ///
+    /// assert_eq!(Bounded::<1, 16>::MIN, Bounded::<1, 16>::clamp(-3));
+    /// assert_eq!(Some(Bounded::clamp(9)), Bounded::<1, 16>::new(9));
+    /// ```
+    pub const fn clamp(value: i64) -> Self {
+        if value < LO {
+            Self::MIN
+        } else if value > HI {
+            Self::MAX
+        } else {
+            Self(value)
+        }
+    }
+
+    /// Table of the first [MAX_VARIANTS] values of the range, `VARIANTS` being sliced out of it.
+    #[cfg(feature = "variants")]
+    const TABLE: [Self; MAX_VARIANTS] = {
+        let mut table = [Self(LO); MAX_VARIANTS];
+        let mut position = 1;
+        while position < MAX_VARIANTS && position < <Self as Enumerated>::SIZE {
+            table[position] = Self(LO + position as i64);
+            position += 1;
+        }
+        table
+    };
+}
+
+impl<const LO: i64, const HI: i64> Enumerated for Bounded<LO, HI> {
+    const SIZE: usize = {
+        assert!(LO <= HI, "`Bounded` requires `LO <= HI`");
+        let size = HI as i128 - LO as i128 + 1;
+        assert!(
+            size <= usize::MAX as i128,
+            "`Bounded` range doesn't fit in `usize`"
+        );
+        size as usize
+    };
+    #[cfg(feature = "variants")]
+    const VARIANTS: &'static [Self] = {
+        assert!(
+            Self::SIZE <= MAX_VARIANTS,
+            "`Bounded` ranges over 256 values do not provide `Enumerated::VARIANTS`"
+        );
+        let table: &'static [Self; MAX_VARIANTS] = &Self::TABLE;
+        table.split_at(Self::SIZE).0
+    };
+    const LAYOUT_HASH: u64 = combine_layout_hash(
+        combine_layout_hash(layout_hash(&["Bounded"]), LO as u64),
+        HI as u64,
+    );
+
+    fn position(&self) -> usize {
+        (self.0 as i128 - LO as i128) as usize
+    }
+
+    fn from_position(position: usize) -> Self {
+        assert!(
+            position < Self::SIZE,
+            "Position {} is out of range of `Bounded<{}, {}>`",
+            position,
+            LO,
+            HI
+        );
+        Self((LO as i128 + position as i128) as i64)
+    }
+}
+
+impl<const LO: i64, const HI: i64> Display for Bounded<LO, HI> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<const LO: i64, const HI: i64> From<Bounded<LO, HI>> for i64 {
+    fn from(value: Bounded<LO, HI>) -> Self {
+        value.0
+    }
+}
+
+/// Fails with [OutOfRange] for values not within `LO..=HI`.
+///
+/// ```
+/// use enum_collections::Bounded;
+///
+/// assert!(Bounded::<0, 23>::try_from(23).is_ok());
+/// assert_eq!(
+///     "Value 24 is out of range 0..=23",
+///     Bounded::<0, 23>::try_from(24).unwrap_err().to_string()
+/// );
+/// ```
+impl<const LO: i64, const HI: i64> TryFrom<i64> for Bounded<LO, HI> {
+    type Error = OutOfRange;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(OutOfRange {
+            value,
+            lo: LO,
+            hi: HI,
+        })
+    }
+}
+
+/// Value not within the range of a [Bounded] integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange {
+    value: i64,
+    lo: i64,
+    hi: i64,
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Value {} is out of range {}..={}",
+            self.value, self.lo, self.hi
+        )
+    }
+}
+
+impl std::error::Error for OutOfRange {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bounded, EnumMap, Enumerated, check_enumerated};
+
+    type Channel = Bounded<1, 16>;
+
+    #[test]
+    fn positions() {
+        assert_eq!(16, Channel::SIZE);
+        assert_eq!(0, Channel::MIN.position());
+        assert_eq!(15, Channel::MAX.position());
+        assert_eq!(Channel::new(10), Some(Channel::from_position(9)));
+        check_enumerated::<Channel>();
+        check_enumerated::<Bounded<-3, 3>>();
+        check_enumerated::<Bounded<5, 5>>();
+    }
+
+    #[test]
+    fn extreme_ranges() {
+        type Top = Bounded<{ i64::MAX - 3 }, { i64::MAX }>;
+        assert_eq!(3, Top::MAX.position());
+        assert_eq!(Top::MAX, Top::from_position(3));
+        check_enumerated::<Top>();
+
+        type Spanning = Bounded<{ i64::MIN }, { i64::MIN + 1 }>;
+        assert_eq!(Spanning::MAX, Spanning::from_position(1));
+        assert_eq!(Spanning::MIN, Spanning::MIN.saturating_sub(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Position 16 is out of range of `Bounded<1, 16>`")]
+    fn from_position_out_of_range() {
+        Channel::from_position(16);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let channel = Channel::new(15).unwrap();
+        assert_eq!(Channel::new(16), channel.checked_add(1));
+        assert_eq!(None, channel.checked_add(2));
+        assert_eq!(None, channel.checked_add(i64::MAX));
+        assert_eq!(None, channel.checked_sub(15));
+        assert_eq!(Channel::MAX, channel.saturating_add(i64::MAX));
+        assert_eq!(Channel::MIN, channel.saturating_sub(i64::MAX));
+        assert_eq!(Channel::MIN, channel.saturating_add(i64::MIN));
+    }
+
+    #[test]
+    fn enum_map() {
+        let mut velocities = EnumMap::<Channel, u8, { Channel::SIZE }>::new_default();
+        velocities[Channel::new(10).unwrap()] = 100;
+        let loud: Vec<i64> = velocities
+            .iter_kv()
+            .filter(|(_, velocity)| **velocity > 0)
+            .map(|(channel, _)| channel.get())
+            .collect();
+        assert_eq!(vec![10], loud);
+    }
+
+    #[cfg(feature = "variants")]
+    #[test]
+    fn variants() {
+        assert_eq!(16, Channel::VARIANTS.len());
+        for (position, channel) in Channel::VARIANTS.iter().enumerate() {
+            assert_eq!(position as i64 + 1, channel.get());
+        }
+        assert_eq!(256, Bounded::<-128, 127>::VARIANTS.len());
+    }
+}
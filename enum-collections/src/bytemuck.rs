@@ -0,0 +1,173 @@
+//! Zero-copy casting of [EnumMap] from and to bytes using the `bytemuck` crate.
+//!
+//! Relies on EnumMap being `#[repr(transparent)]` over `[V; N]`.
+
+use bytemuck::{Pod, PodCastError, TransparentWrapper, Zeroable};
+
+use crate::{EnumMap, Enumerated};
+
+// SAFETY: EnumMap is `#[repr(transparent)]` over `[V; N]`, the `PhantomData<K>` being zero-sized.
+// An array of zeroable values is zeroable.
+unsafe impl<K: Enumerated, V: Zeroable, const N: usize> Zeroable for EnumMap<K, V, N> {}
+
+// SAFETY: EnumMap is `#[repr(transparent)]` over `[V; N]`, which is `Pod` for `Pod` values.
+// There is no padding and no invalid bit pattern beyond those of `V`.
+unsafe impl<K: Enumerated + 'static, V: Pod, const N: usize> Pod for EnumMap<K, V, N> {}
+
+// SAFETY: EnumMap is `#[repr(transparent)]` over `[V; N]`, the only non-zero-sized field.
+unsafe impl<K: Enumerated, V, const N: usize> TransparentWrapper<[V; N]> for EnumMap<K, V, N> {}
+
+impl<K: Enumerated + 'static, V: Pod, const N: usize> EnumMap<K, V, N> {
+    /// Reinterprets bytes as an EnumMap, without copying.
+    ///
+    /// Returns an error if the length of the bytes doesn't match the size of the EnumMap,
+    /// or if the bytes aren't aligned to `V`.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Channel {
+    ///    Red,
+    ///    Green,
+    ///    Blue,
+    /// }
+    ///
+    /// let bytes = [10u8, 20, 30];
+    /// let enum_map = EnumMap::<Channel, u8, { Channel::SIZE }>::from_bytes(&bytes).unwrap();
+    /// assert_eq!(20, enum_map[Channel::Green]);
+    /// assert!(EnumMap::<Channel, u8, { Channel::SIZE }>::from_bytes(&bytes[1..]).is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, PodCastError> {
+        bytemuck::try_from_bytes(bytes)
+    }
+
+    /// Reinterprets mutable bytes as a mutable EnumMap, without copying.
+    ///
+    /// Returns an error if the length of the bytes doesn't match the size of the EnumMap,
+    /// or if the bytes aren't aligned to `V`.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Channel {
+    ///    Red,
+    ///    Green,
+    ///    Blue,
+    /// }
+    ///
+    /// let mut bytes = [10u8, 20, 30];
+    /// let enum_map = EnumMap::<Channel, u8, { Channel::SIZE }>::from_bytes_mut(&mut bytes).unwrap();
+    /// enum_map[Channel::Blue] = 42;
+    /// assert_eq!([10, 20, 42], bytes);
+    /// ```
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, PodCastError> {
+        bytemuck::try_from_bytes_mut(bytes)
+    }
+
+    /// Views the EnumMap as bytes, without copying. Values are laid out in the order of their variants' positions.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Channel {
+    ///    Red,
+    ///    Green,
+    ///    Blue,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Channel, u16, { Channel::SIZE }>::new_inspect(|channel| match channel {
+    ///     Channel::Red => 1,
+    ///     Channel::Green => 2,
+    ///     Channel::Blue => 3,
+    /// });
+    /// let expected: Vec<u8> = [1u16, 2, 3].iter().flat_map(|value| value.to_ne_bytes()).collect();
+    /// assert_eq!(expected.as_slice(), enum_map.as_bytes());
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+
+    /// Views the EnumMap as mutable bytes, without copying.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Channel {
+    ///    Red,
+    ///    Green,
+    ///    Blue,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Channel, u8, { Channel::SIZE }>::new_default();
+    /// enum_map.as_bytes_mut().copy_from_slice(&[1, 2, 3]);
+    /// assert_eq!(3, enum_map[Channel::Blue]);
+    /// ```
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::bytes_of_mut(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::{PodCastError, TransparentWrapper, Zeroable};
+
+    use crate::{EnumMap, Enumerated};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Counter {
+        Hits,
+        Misses,
+        Evictions,
+    }
+
+    type Counters = EnumMap<Counter, u32, { Counter::SIZE }>;
+
+    #[test]
+    fn layout() {
+        assert_eq!(size_of::<[u32; 3]>(), size_of::<Counters>());
+        assert_eq!(align_of::<[u32; 3]>(), align_of::<Counters>());
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut counters = Counters::zeroed();
+        counters[Counter::Misses] = 7;
+        counters[Counter::Evictions] = u32::MAX;
+
+        let bytes = counters.as_bytes().to_vec();
+        assert_eq!(12, bytes.len());
+        assert_eq!(&7u32.to_ne_bytes(), &bytes[4..8]);
+
+        // Vec<u8> isn't guaranteed to be aligned to u32, copy into an aligned buffer
+        let mut aligned = [0u32; 3];
+        bytemuck::bytes_of_mut(&mut aligned).copy_from_slice(&bytes);
+        let cast = Counters::from_bytes(bytemuck::bytes_of(&aligned)).unwrap();
+        assert_eq!(counters, *cast);
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let aligned = [0u32; 4];
+        let bytes = bytemuck::bytes_of(&aligned);
+        assert_eq!(
+            Err(PodCastError::SizeMismatch),
+            Counters::from_bytes(&bytes[..8]).map(|_| ())
+        );
+        assert_eq!(
+            Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned),
+            Counters::from_bytes(&bytes[1..13]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn wrap_array() {
+        let mut buffer = [1u32, 2, 3];
+        let counters = Counters::wrap_mut(&mut buffer);
+        counters[Counter::Hits] += 10;
+        assert_eq!([11, 2, 3], buffer);
+
+        let buffers = [[1u32, 2, 3], [4, 5, 6]];
+        let counters: &[Counters] = Counters::wrap_slice(&buffers);
+        assert_eq!(5, counters[1][Counter::Misses]);
+    }
+}
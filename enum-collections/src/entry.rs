@@ -0,0 +1,232 @@
+//! Entry API for EnumMaps of optional values, `EnumMap<K, Option<V>, N>`.
+//!
+//! Mirrors [std::collections::hash_map::Entry]. An entry is a single variant's slot,
+//! vacant if the slot holds `None` and occupied otherwise.
+
+/// A view into a single variant's slot of an EnumMap of optional values, which may either be vacant or occupied.
+///
+/// Constructed by [crate::EnumMap::entry].
+pub enum Entry<'a, K, V> {
+    /// The slot holds a value.
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// The slot holds `None`.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// A view into an occupied slot. Part of the [Entry] enum.
+pub struct OccupiedEntry<'a, K, V> {
+    key: K,
+    slot: &'a mut Option<V>,
+}
+
+/// A view into a vacant slot. Part of the [Entry] enum.
+pub struct VacantEntry<'a, K, V> {
+    key: K,
+    slot: &'a mut Option<V>,
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub(crate) fn new(key: K, slot: &'a mut Option<V>) -> Self {
+        if slot.is_some() {
+            Entry::Occupied(OccupiedEntry { key, slot })
+        } else {
+            Entry::Vacant(VacantEntry { key, slot })
+        }
+    }
+
+    /// Returns a reference to the entry's key.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated, Debug, PartialEq)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// assert_eq!(&Letter::B, enum_map.entry(Letter::B).key());
+    /// ```
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty.
+    /// Returns a mutable reference to the value in the entry.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// *enum_map.entry(Letter::A).or_insert(40) += 2;
+    /// *enum_map.entry(Letter::A).or_insert(0) += 0;
+    /// assert_eq!(Some(42), enum_map[Letter::A]);
+    /// ```
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty.
+    /// The function is not called if the entry is occupied.
+    /// Returns a mutable reference to the value in the entry.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<Vec<i32>>, { Letter::SIZE }>::new_option();
+    /// enum_map.entry(Letter::A).or_insert_with(Vec::new).push(42);
+    /// enum_map.entry(Letter::A).or_insert_with(Vec::new).push(24);
+    /// assert_eq!(Some(vec![42, 24]), enum_map[Letter::A]);
+    /// assert_eq!(None, enum_map[Letter::B]);
+    /// ```
+    pub fn or_insert_with(self, default: impl FnOnce() -> V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// enum_map.entry(Letter::A).and_modify(|value| *value += 1).or_insert(42);
+    /// assert_eq!(Some(42), enum_map[Letter::A]);
+    /// enum_map.entry(Letter::A).and_modify(|value| *value += 1).or_insert(42);
+    /// assert_eq!(Some(43), enum_map[Letter::A]);
+    /// ```
+    pub fn and_modify(self, modify: impl FnOnce(&mut V)) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                modify(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K, V: Default> Entry<'a, K, V> {
+    /// Ensures a value is in the entry by inserting `V::default()` if empty.
+    /// Returns a mutable reference to the value in the entry.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// *enum_map.entry(Letter::B).or_default() += 1;
+    /// assert_eq!(Some(1), enum_map[Letter::B]);
+    /// ```
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// Gets a reference to the key of the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        self.slot.as_ref().expect("Occupied entry holds a value")
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.slot.as_mut().expect("Occupied entry holds a value")
+    }
+
+    /// Converts the entry into a mutable reference to its value, bound to the lifetime of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        self.slot.as_mut().expect("Occupied entry holds a value")
+    }
+
+    /// Sets the value of the entry, returning the old value.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Takes the value out of the entry, leaving `None` in the map.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated, Entry};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// enum_map.insert(Letter::A, 42);
+    /// if let Entry::Occupied(entry) = enum_map.entry(Letter::A) {
+    ///     assert!(matches!(entry.key(), Letter::A));
+    ///     assert_eq!(42, entry.remove());
+    /// }
+    /// assert_eq!(None, enum_map[Letter::A]);
+    /// ```
+    pub fn remove(self) -> V {
+        self.slot.take().expect("Occupied entry holds a value")
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    /// Gets a reference to the key that would be used when inserting a value through the entry.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated, Entry};
+    /// #[derive(Enumerated, Debug, PartialEq)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// if let Entry::Vacant(entry) = enum_map.entry(Letter::A) {
+    ///     assert_eq!(&Letter::A, entry.key());
+    ///     assert_eq!(Letter::A, entry.into_key());
+    /// }
+    /// ```
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key, leaving the slot vacant.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry, returning a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.slot.insert(value)
+    }
+}
@@ -1,6 +1,20 @@
 /// Provides means to map enum values to positions in arrays backing an EnumMap/EnumTable.
-/// Not intended to be implemented by hand. Deriving it with `#[derive(Enumerated)]`
-/// attribute macro is preferred.
+///
+/// Deriving it with `#[derive(Enumerated)]` is preferred, [crate::remote_enumerated] covers enums
+/// that can't be annotated. Implementing it by hand is supported, as long as the contract below holds.
+/// Use [check_enumerated] to validate a hand-written implementation in tests.
+///
+/// # Contract
+///
+/// - Positions are dense: every value has a position in `0..SIZE` and every position in `0..SIZE`
+///   belongs to exactly one value.
+/// - [Self::from_position] is the inverse of [Self::position], `K::from_position(p).position() == p`
+///   for each `p` in `0..SIZE`. It panics for positions not lower than `SIZE`.
+/// - With the `variants` feature, `VARIANTS` holds `SIZE` values, `VARIANTS[i].position() == i`.
+/// - [Self::VARIANT_NAMES] is either empty, or holds a name for each position.
+///
+/// EnumMap indexes its array by positions. A broken contract never causes undefined behavior,
+/// but may lead to panics or to values being stored under the wrong key.
 ///
 /// ```
 /// use enum_collections::Enumerated;
@@ -11,13 +25,225 @@
 /// }
 ///
 /// assert_eq!(Letter::SIZE, 2);
+/// assert!(matches!(Letter::from_position(1), Letter::B));
+/// ```
+///
+/// Implemented by hand:
+/// ```
+/// use enum_collections::{Enumerated, check_enumerated};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Digit(u8);
+///
+/// impl Enumerated for Digit {
+///     const SIZE: usize = 10;
+///     # #[cfg(feature = "variants")]
+///     # const VARIANTS: &'static [Self] = &[Digit(0), Digit(1), Digit(2), Digit(3), Digit(4), Digit(5), Digit(6), Digit(7), Digit(8), Digit(9)];
+///
+///     fn position(&self) -> usize {
+///         self.0 as usize
+///     }
+///
+///     fn from_position(position: usize) -> Self {
+///         assert!(position < Self::SIZE, "Position {} is out of range of `Digit`", position);
+///         Digit(position as u8)
+///     }
+/// }
+///
+/// check_enumerated::<Digit>();
 /// ```
-#[doc(hidden)]
 pub trait Enumerated: Sized + 'static {
-    /// Maps an enum to a unique position in an array.
-    fn position(self) -> usize;
-    const SIZE: usize = 0;
+    /// Maps an enum to a unique position in an array, lower than [Self::SIZE].
+    /// Takes the key by reference, keys don't need to be `Copy`. Method calls on owned keys, `key.position()`,
+    /// borrow them automatically.
+    fn position(&self) -> usize;
+    /// Maps a position back to the enum variant. Inverse of [Self::position].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is not lower than [Self::SIZE].
+    fn from_position(position: usize) -> Self;
+    /// Number of variants, i.e. of distinct positions. The `N` of EnumMaps keyed by this type.
+    const SIZE: usize;
     /// All variants of the enum. Sorted by their discriminants ASC.
+    /// Only generated with the `variants` feature, [Self::from_position] is used internally instead.
+    ///
+    /// Generic implementations of the `ext` feature, `Option<T>`, `Result<T, E>`, tuples and arrays, build it
+    /// at compile time out of their components' `VARIANTS`. Tables over 16 MiB fail to compile.
     #[cfg(feature = "variants")]
     const VARIANTS: &'static [Self];
+    /// Names of the variants, in the order of their positions. Derived for enums, empty for other types.
+    /// Lets the [crate::em] macros report variants missing from the map in readable terms.
+    const VARIANT_NAMES: &'static [&'static str] = &[];
+    /// Fingerprint of the layout, i.e. of which variant is at which position.
+    /// Derived enums hash the names of their variants in order, so reordering, inserting, removing
+    /// or renaming a variant changes it. Positional encodings embed it and refuse to decode
+    /// values persisted with a different layout.
+    ///
+    /// Hand-written implementations default to a hash of [Self::SIZE] only.
+    const LAYOUT_HASH: u64 = combine_layout_hash(FNV_OFFSET_BASIS, Self::SIZE as u64);
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// FNV-1a hash of names in order, each name terminated by `0xff`, a byte never present in UTF-8.
+/// Code generated by the derive macro and [crate::remote_enumerated] calls it to hash variant and field names.
+pub const fn layout_hash(names: &[&str]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut name = 0;
+    while name < names.len() {
+        let bytes = names[name].as_bytes();
+        let mut byte = 0;
+        while byte < bytes.len() {
+            hash = (hash ^ bytes[byte] as u64).wrapping_mul(FNV_PRIME);
+            byte += 1;
+        }
+        hash = (hash ^ 0xff).wrapping_mul(FNV_PRIME);
+        name += 1;
+    }
+    hash
+}
+
+/// Mixes a layout hash of a component, e.g. a struct's field, into a layout hash.
+/// Code generated by the derive macro calls it to combine hashes of fields.
+pub const fn combine_layout_hash(hash: u64, other: u64) -> u64 {
+    (hash ^ other).wrapping_mul(FNV_PRIME)
+}
+
+/// Checks an implementation of [Enumerated] against the trait's contract, panicking with a description
+/// of the first violation found. Intended for tests of hand-written implementations.
+///
+/// Each position in `0..SIZE` must map back to itself. `VARIANTS`, if generated, and [Enumerated::VARIANT_NAMES],
+/// if not empty, must list exactly `SIZE` entries, in the order of positions. With the `variants` feature,
+/// types not providing `VARIANTS`, e.g. generic types implemented by the `ext` feature, fail to compile.
+///
+/// ```should_panic
+/// use enum_collections::{Enumerated, check_enumerated};
+///
+/// #[derive(Clone, Copy)]
+/// enum Switch {
+///     Off,
+///     On,
+/// }
+///
+/// impl Enumerated for Switch {
+///     const SIZE: usize = 2;
+///     # #[cfg(feature = "variants")]
+///     # const VARIANTS: &'static [Self] = &[Switch::Off, Switch::On];
+///
+///     fn position(&self) -> usize {
+///         0 // Wrong, `On` must be at position 1
+///     }
+///
+///     fn from_position(position: usize) -> Self {
+///         if position == 0 { Switch::Off } else { Switch::On }
+///     }
+/// }
+///
+/// check_enumerated::<Switch>(); // Panics, position 1 maps back to 0
+/// ```
+pub fn check_enumerated<K: Enumerated>() {
+    let name = std::any::type_name::<K>();
+    for position in 0..K::SIZE {
+        let actual = K::from_position(position).position();
+        assert_eq!(
+            position, actual,
+            "`{}::from_position({})` has position {}",
+            name, position, actual
+        );
+    }
+    #[cfg(feature = "variants")]
+    {
+        assert_eq!(
+            K::SIZE,
+            K::VARIANTS.len(),
+            "`{}::VARIANTS` doesn't hold `SIZE` variants",
+            name
+        );
+        for (position, variant) in K::VARIANTS.iter().enumerate() {
+            let actual = variant.position();
+            assert_eq!(
+                position, actual,
+                "`{}::VARIANTS[{}]` has position {}",
+                name, position, actual
+            );
+        }
+    }
+    assert!(
+        K::VARIANT_NAMES.is_empty() || K::VARIANT_NAMES.len() == K::SIZE,
+        "`{}::VARIANT_NAMES` holds {} names, but `SIZE` is {}",
+        name,
+        K::VARIANT_NAMES.len(),
+        K::SIZE
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Enumerated, check_enumerated};
+
+    #[derive(Enumerated, Clone, Copy)]
+    enum Letter {
+        A,
+        B,
+        C,
+    }
+
+    #[derive(Enumerated, Clone, Copy)]
+    struct Pair(Letter, Letter);
+
+    #[test]
+    fn derived() {
+        check_enumerated::<Letter>();
+        check_enumerated::<Pair>();
+    }
+
+    #[derive(Clone, Copy)]
+    struct Shifted(u8);
+
+    impl Enumerated for Shifted {
+        const SIZE: usize = 4;
+        #[cfg(feature = "variants")]
+        const VARIANTS: &'static [Self] = &[Shifted(0), Shifted(1), Shifted(2), Shifted(3)];
+
+        fn position(&self) -> usize {
+            // Off by one, positions must start at zero
+            self.0 as usize + 1
+        }
+
+        fn from_position(position: usize) -> Self {
+            Shifted(position as u8)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "from_position(0)` has position 1")]
+    fn broken_positions() {
+        check_enumerated::<Shifted>();
+    }
+
+    #[derive(Clone, Copy)]
+    struct Named(bool);
+
+    impl Enumerated for Named {
+        const SIZE: usize = 2;
+        #[cfg(feature = "variants")]
+        const VARIANTS: &'static [Self] = &[Named(false), Named(true)];
+        const VARIANT_NAMES: &'static [&'static str] = &["No"];
+
+        fn position(&self) -> usize {
+            self.0 as usize
+        }
+
+        fn from_position(position: usize) -> Self {
+            Named(position == 1)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "VARIANT_NAMES` holds 1 names, but `SIZE` is 2")]
+    fn missing_names() {
+        check_enumerated::<Named>();
+    }
 }
@@ -1,45 +1,112 @@
-use crate::Enumerated;
+use crate::{Entry, Enumerated};
 use std::{
     array,
-    fmt::Debug,
     marker::PhantomData,
     ops::{Index, IndexMut},
 };
 
 /// Creates an EnumMap with user-provided values.
+///
+/// Variants are listed by name after the key and value types. Several variants may share a value
+/// with `A | B => value`, `_ => value` covers all variants not listed. Every variant must be covered,
+/// otherwise compilation fails with an error naming the missing variants.
 /// ```
 /// use enum_collections::{em, Enumerated, EnumMap};
 /// #[derive(Enumerated)]
 /// enum Letter {
 ///    A,
 ///    B,
+///    C,
 /// }
 ///
-/// let enum_map = em!(Letter, i32,  A => 42, B => 24);
+/// let enum_map = em!(Letter, i32, A => 42, B | C => 24);
 /// assert_eq!(42, enum_map[Letter::A]);
+/// assert_eq!(24, enum_map[Letter::C]);
+///
+/// let enum_map = em!(Letter, i32, B => 24, _ => 0);
+/// assert_eq!(0, enum_map[Letter::A]);
 /// assert_eq!(24, enum_map[Letter::B]);
 /// ```
 ///
+/// A closure receives a reference to each variant instead:
+/// ```
+/// # use enum_collections::{em, Enumerated, EnumMap};
+/// # #[derive(Enumerated, Clone, Copy)]
+/// # enum Letter {
+/// #    A,
+/// #    B,
+/// # }
+/// let enum_map = em!(Letter, usize, |letter| letter.position() * 10);
+/// assert_eq!(10, enum_map[Letter::B]);
+/// ```
+///
+/// Without the types, keys are patterns and the types are inferred from the map's annotation:
+/// ```
+/// # use enum_collections::{em, Enumerated, EnumMap};
+/// # #[derive(Enumerated)]
+/// # enum Letter {
+/// #    A,
+/// #    B,
+/// # }
+/// let enum_map: EnumMap<Letter, i32, { Letter::SIZE }> = em! {
+///     Letter::A => 42,
+///     Letter::B => 24,
+/// };
+/// assert_eq!(24, enum_map[Letter::B]);
+/// ```
+///
+/// Missing variants are reported at compile time:
+/// ```compile_fail
+/// # use enum_collections::{em, Enumerated, EnumMap};
+/// # #[derive(Enumerated)]
+/// # enum Letter {
+/// #    A,
+/// #    B,
+/// # }
+/// let enum_map = em!(Letter, i32, A => 42); // em! doesn't cover all variants, missing: B
+/// ```
 #[macro_export]
 macro_rules! em {
+    ($ktp:ty, $vtp:ty, |$key:pat_param| $value:expr $(,)?) => {
+        $crate::EnumMap::<$ktp, $vtp, { <$ktp as $crate::Enumerated>::SIZE }>::new_inspect(
+            |$key| $value,
+        )
+    };
 
-    ($ktp:ty, $vtp:ty, $($x:ident=>$y:expr),* ) => {
-        enum_collections::EnumMap::<$ktp, $vtp, {<$ktp>::SIZE}>::new_inspect(|letter| {
-            match letter {
-                $(<$ktp>::$x => $y,)*
+    ($ktp:ty, $vtp:ty $(, $($($x:tt)|+ => $y:expr),+)? $(,)?) => {{
+        const {
+            $crate::__check_em_keys(
+                <$ktp as $crate::Enumerated>::VARIANT_NAMES,
+                &[$($($(stringify!($x)),+),+)?],
+            )
+        };
+        $crate::EnumMap::<$ktp, $vtp, { <$ktp as $crate::Enumerated>::SIZE }>::new_inspect(|key| {
+            #[allow(unreachable_patterns)]
+            match key {
+                $($($($crate::__em_key!($ktp, $x))|+ => $y,)+)?
+                // Coverage is checked above
+                _ => unreachable!(),
             }
         })
-    };
+    }};
 
+    ($($x:pat => $y:expr),+ $(,)?) => {
+        $crate::EnumMap::new_inspect(|key| match key {
+            $($x => $y,)+
+        })
+    };
 }
 
 /// Initializes an EnumMap with default values for all variants not explicitly specified.
+///
+/// Accepts the same forms as [em], values of variants not listed being `Default::default()`.
 /// ```
 /// use enum_collections::{em_default, Enumerated, EnumMap};
 /// #[derive(Enumerated)]
 /// enum Letter {
 ///    A,
 ///    B,
+///    C,
 /// }
 ///
 /// // One non-default value
@@ -48,18 +115,31 @@ macro_rules! em {
 /// assert_eq!(i32::default(), enum_map[Letter::B]);
 ///
 /// // All default
-///
-/// let enum_map = em_default!(Letter, i32,);
+/// let enum_map = em_default!(Letter, i32);
 /// assert_eq!(i32::default(), enum_map[Letter::A]);
-/// assert_eq!(i32::default(), enum_map[Letter::B]);
-/// ```
 ///
+/// // Inferred from the annotation
+/// let enum_map: EnumMap<Letter, i32, { Letter::SIZE }> = em_default! { Letter::A | Letter::B => 1 };
+/// assert_eq!(1, enum_map[Letter::B]);
+/// assert_eq!(0, enum_map[Letter::C]);
+/// ```
 #[macro_export]
 macro_rules! em_default {
-    ($ktp:ty, $vtp:ty, $($x:ident=>$y:expr),* ) => {
-        EnumMap::<$ktp, $vtp, {<$ktp>::SIZE}>::new_inspect(|letter| {
-            match letter {
-                $(<$ktp>::$x => $y,)*
+    ($ktp:ty, $vtp:ty $(, $($($x:tt)|+ => $y:expr),+)? $(,)?) => {
+        $crate::EnumMap::<$ktp, $vtp, { <$ktp as $crate::Enumerated>::SIZE }>::new_inspect(|key| {
+            #[allow(unreachable_patterns)]
+            match key {
+                $($($($crate::__em_key!($ktp, $x))|+ => $y,)+)?
+                _ => Default::default(),
+            }
+        })
+    };
+
+    ($($x:pat => $y:expr),* $(,)?) => {
+        $crate::EnumMap::new_inspect(|key| {
+            #[allow(unreachable_patterns)]
+            match key {
+                $($x => $y,)*
                 _ => Default::default(),
             }
         })
@@ -68,6 +148,7 @@ macro_rules! em_default {
 
 /// Initializes an EnumMap with `Option::None` for all variants not explicitly specified.
 ///
+/// Accepts the same forms as [em_default], values being wrapped in `Some`.
 /// ```
 /// use enum_collections::{em_option, Enumerated, EnumMap};
 /// #[derive(Enumerated)]
@@ -79,19 +160,113 @@ macro_rules! em_default {
 /// let enum_map = em_option!(Letter, i32, A => 42);
 /// assert_eq!(Some(42), enum_map[Letter::A]);
 /// assert_eq!(None, enum_map[Letter::B]);
+///
+/// let enum_map: EnumMap<Letter, Option<i32>, { Letter::SIZE }> = em_option! { Letter::B => 24 };
+/// assert_eq!(None, enum_map[Letter::A]);
+/// assert_eq!(Some(24), enum_map[Letter::B]);
 /// ```
 #[macro_export]
 macro_rules! em_option {
-    ($ktp:ty, $vtp:ty, $($x:ident=>$y:expr),* ) => {
-        EnumMap::<$ktp, Option<$vtp>, {<$ktp>::SIZE}>::new_inspect(|letter| {
-            match letter {
-                $(<$ktp>::$x => Some($y),)*
+    ($ktp:ty, $vtp:ty $(, $($($x:tt)|+ => $y:expr),+)? $(,)?) => {
+        $crate::EnumMap::<$ktp, Option<$vtp>, { <$ktp as $crate::Enumerated>::SIZE }>::new_inspect(
+            |key| {
+                #[allow(unreachable_patterns)]
+                match key {
+                    $($($($crate::__em_key!($ktp, $x))|+ => Some($y),)+)?
+                    _ => None,
+                }
+            },
+        )
+    };
+
+    ($($x:pat => $y:expr),* $(,)?) => {
+        $crate::EnumMap::new_inspect(|key| {
+            #[allow(unreachable_patterns)]
+            match key {
+                $($x => Some($y),)*
                 _ => None,
             }
         })
     };
 }
 
+/// Pattern of a key listed in [em], either a variant name of `$ktp`, or `_`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __em_key {
+    ($ktp:ty, _) => {
+        _
+    };
+    ($ktp:ty, $x:ident) => {
+        <$ktp>::$x
+    };
+}
+
+/// Fails const evaluation, naming the variants of `variants` not found in `keys`, unless `keys` contain `_`.
+#[doc(hidden)]
+pub const fn __check_em_keys(variants: &[&str], keys: &[&str]) {
+    if contains(keys, "_") {
+        return;
+    }
+    if variants.is_empty() {
+        panic!("em! can only name variants of derived enums, use `_` to cover the remaining keys");
+    }
+
+    const PREFIX: &[u8] = b"em! doesn't cover all variants, missing: ";
+    let mut message = [0u8; 512];
+    let mut length = append(&mut message, 0, PREFIX);
+    let mut missing = 0;
+    let mut variant = 0;
+    while variant < variants.len() {
+        if !contains(keys, variants[variant]) {
+            let separator: &[u8] = if missing == 0 { b"" } else { b", " };
+            let name = variants[variant].as_bytes();
+            // Names not fitting the message are left out
+            if length + separator.len() + name.len() <= message.len() {
+                length = append(&mut message, length, separator);
+                length = append(&mut message, length, name);
+            }
+            missing += 1;
+        }
+        variant += 1;
+    }
+    if missing > 0 {
+        match core::str::from_utf8(message.split_at(length).0) {
+            // Only a plain `&str` argument is supported by const panics
+            Ok(message) => panic!("{}", message),
+            Err(_) => panic!("em! doesn't cover all variants"),
+        }
+    }
+}
+
+const fn contains(haystack: &[&str], needle: &str) -> bool {
+    let mut index = 0;
+    while index < haystack.len() {
+        let (candidate, needle) = (haystack[index].as_bytes(), needle.as_bytes());
+        if candidate.len() == needle.len() {
+            let mut byte = 0;
+            while byte < needle.len() && candidate[byte] == needle[byte] {
+                byte += 1;
+            }
+            if byte == needle.len() {
+                return true;
+            }
+        }
+        index += 1;
+    }
+    false
+}
+
+const fn append(buffer: &mut [u8], mut length: usize, bytes: &[u8]) -> usize {
+    let mut index = 0;
+    while index < bytes.len() {
+        buffer[length] = bytes[index];
+        length += 1;
+        index += 1;
+    }
+    length
+}
+
 #[cfg(test)]
 mod macro_test {
     use crate::{EnumMap, Enumerated};
@@ -102,12 +277,69 @@ mod macro_test {
         B,
     }
 
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Grade {
+        Low,
+        Medium,
+        High,
+    }
+
     #[test]
     fn test_macro() {
         let enum_map = em_default!(Letter, i32,  A=>42);
         assert_eq!(42, enum_map[Letter::A]);
         assert_eq!(i32::default(), enum_map[Letter::B]);
     }
+
+    #[test]
+    fn test_em_grouped_and_catch_all() {
+        let enum_map = em!(Grade, &str, Low | Medium => "pass", High => "merit",);
+        assert_eq!(["pass", "pass", "merit"], enum_map.data);
+
+        let enum_map = em!(Grade, u8, Medium => 5, _ => 1);
+        assert_eq!([1, 5, 1], enum_map.data);
+    }
+
+    #[test]
+    fn test_em_closure() {
+        let enum_map = em!(Grade, Grade, |grade| *grade);
+        assert_eq!(Grade::High, enum_map[Grade::High]);
+    }
+
+    #[test]
+    fn test_em_inferred() {
+        use Grade::*;
+        let enum_map: EnumMap<Grade, u8, { Grade::SIZE }> = em! { Low => 1, Medium | High => 2 };
+        assert_eq!([1, 2, 2], enum_map.data);
+
+        let enum_map: EnumMap<Grade, u8, { Grade::SIZE }> = em_default! { High => 3 };
+        assert_eq!([0, 0, 3], enum_map.data);
+
+        let enum_map: EnumMap<Grade, Option<u8>, { Grade::SIZE }> = em_option! {};
+        assert_eq!([None; 3], enum_map.data);
+    }
+
+    #[test]
+    fn test_em_default_and_option_forms() {
+        let enum_map = em_default!(Grade, u8);
+        assert_eq!([0; 3], enum_map.data);
+
+        let enum_map = em_option!(Grade, u8, Low | High => 7);
+        assert_eq!([Some(7), None, Some(7)], enum_map.data);
+    }
+
+    #[test]
+    #[should_panic(expected = "em! doesn't cover all variants, missing: Medium, High")]
+    fn test_em_missing_variants() {
+        crate::__check_em_keys(Grade::VARIANT_NAMES, &["Low"]);
+    }
+
+    #[test]
+    fn test_em_covered_variants() {
+        crate::__check_em_keys(Grade::VARIANT_NAMES, &["High", "Low", "Medium"]);
+        crate::__check_em_keys(Grade::VARIANT_NAMES, &["_"]);
+        crate::__check_em_keys(&[], &["_"]);
+    }
 }
 
 /// A map of enum variants to values. EnumMap is a fixed-size map, where each variant of the enum
@@ -124,6 +356,13 @@ mod macro_test {
 ///
 /// Debug and Eq are optional features. They are enabled by default.
 ///
+/// # Layout
+///
+/// EnumMap is `#[repr(transparent)]` over `[V; N]`. It has exactly the same size, alignment and
+/// memory layout as the array, the value of the variant at position `i` being stored at index `i`.
+/// With the `bytemuck` and `zerocopy` features, EnumMaps of plain-old-data values can be cast
+/// from and to bytes without copying.
+///
 /// # Examples
 ///
 /// ```
@@ -198,11 +437,48 @@ mod macro_test {
 /// assert_eq!("{A: 42, B: 42}", format!("{:?}", enum_map_debug));
 ///
 /// ```
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
 pub struct EnumMap<K: Enumerated, V, const N: usize> {
     pub(crate) data: [V; N],
     pub(crate) _key: PhantomData<K>,
 }
 
+/// Iterator over the variants of `K`, in the order of their positions.
+pub type Keys<K> = std::iter::Map<std::ops::Range<usize>, fn(usize) -> K>;
+
+/// Variants of `K` at positions `0..size`.
+pub(crate) fn keys<K: Enumerated>(size: usize) -> Keys<K> {
+    (0..size).map(K::from_position)
+}
+
+/// Builds the map's array on the heap value by value, without placing the whole array on the stack.
+fn boxed_from_fn<K: Enumerated, V, const N: usize>(
+    mut value: impl FnMut(usize) -> V,
+) -> Box<EnumMap<K, V, N>> {
+    let mut values = Vec::with_capacity(N);
+    for position in 0..N {
+        values.push(value(position));
+    }
+    let data: Box<[V; N]> = match values.into_boxed_slice().try_into() {
+        Ok(data) => data,
+        Err(_) => unreachable!("Exactly N values are pushed"),
+    };
+    // SAFETY: EnumMap is `#[repr(transparent)]`, its only non-zero-sized field is `data: [V; N]`, `_key` being
+    // a `PhantomData`. `#[repr(transparent)]` guarantees the EnumMap has the same size, alignment and layout
+    // as `[V; N]`, the pointer is valid for the EnumMap and the allocation is released with the same layout.
+    // Each of the N values is initialized and owned by the box, none of them is dropped or duplicated.
+    unsafe { Box::from_raw(Box::into_raw(data).cast::<EnumMap<K, V, N>>()) }
+}
+
 impl<K: Enumerated, V: Default, const N: usize> EnumMap<K, V, N> {
     /// Creates a new EnumMap with type's default values for each variant.
     ///
@@ -224,6 +500,26 @@ impl<K: Enumerated, V: Default, const N: usize> EnumMap<K, V, N> {
             _key: PhantomData,
         }
     }
+
+    /// Creates a new EnumMap with type's default values for each variant, directly on the heap.
+    ///
+    /// Values are moved to the heap one by one, the whole map is never placed on the stack.
+    /// Suitable for maps too large for the stack, e.g. keyed by enums with hundreds of variants.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let enum_map: Box<EnumMap<Letter, [u64; 32], { Letter::SIZE }>> = EnumMap::new_boxed_default();
+    /// assert_eq!([0; 32], enum_map[Letter::B]);
+    /// ```
+    pub fn new_boxed_default() -> Box<Self> {
+        boxed_from_fn(|_| V::default())
+    }
     /// Sets all values to `V::default()`.
     ///
     /// ```
@@ -249,6 +545,8 @@ impl<K: Enumerated, V: Default, const N: usize> EnumMap<K, V, N> {
 impl<K: Enumerated, V, const N: usize> EnumMap<K, Option<V>, N> {
     /// Creates a new EnumMap with `Option::None` set for each variant.
     /// Convenience constructor over `EnumMap::new` for optional values.
+    /// For values without a niche, e.g. `u64` or `f32`, [crate::EnumOptionMap] stores a `bool` per variant
+    /// instead of padding every `Option`, e.g. 9 instead of 16 bytes per `u64`.
     ///
     /// ```
     /// use enum_collections::{EnumMap, Enumerated};
@@ -296,6 +594,236 @@ impl<K: Enumerated, V, const N: usize> EnumMap<K, Option<V>, N> {
             self.data[idx] = None;
         }
     }
+
+    /// Inserts a value for the given variant, returning the previous value, if any.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// assert_eq!(None, enum_map.insert(Letter::A, 42));
+    /// assert_eq!(Some(42), enum_map.insert(Letter::A, 24));
+    /// assert_eq!(Some(24), enum_map[Letter::A]);
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.data[key.position()].replace(value)
+    }
+
+    /// Removes the value of the given variant, returning it, if any. The variant's value is `None` afterwards.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// enum_map.insert(Letter::A, 42);
+    /// assert_eq!(Some(42), enum_map.remove(&Letter::A));
+    /// assert_eq!(None, enum_map.remove(&Letter::A));
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.data[key.position()].take()
+    }
+
+    /// Returns `true` if the given variant holds a value.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// enum_map.insert(Letter::A, 42);
+    /// assert!(enum_map.contains_key(&Letter::A));
+    /// assert!(!enum_map.contains_key(&Letter::B));
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.data[key.position()].is_some()
+    }
+
+    /// Returns the number of variants holding a value.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// assert_eq!(0, enum_map.len());
+    /// enum_map.insert(Letter::B, 42);
+    /// assert_eq!(1, enum_map.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.data.iter().filter(|value| value.is_some()).count()
+    }
+
+    /// Returns `true` if no variant holds a value.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// assert!(enum_map.is_empty());
+    /// enum_map.insert(Letter::B, 42);
+    /// assert!(!enum_map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.data.iter().all(Option::is_none)
+    }
+
+    /// Gets the given variant's entry for in-place manipulation.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut counts = EnumMap::<Letter, Option<u32>, { Letter::SIZE }>::new_option();
+    /// for letter in [Letter::A, Letter::A, Letter::B] {
+    ///     *counts.entry(letter).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(Some(2), counts[Letter::A]);
+    /// assert_eq!(Some(1), counts[Letter::B]);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let position = key.position();
+        Entry::new(key, &mut self.data[position])
+    }
+
+    /// Returns a mutable reference to the given variant's value, inserting the result of the function first,
+    /// if the variant holds no value.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// assert_eq!(42, *enum_map.get_or_insert_with(Letter::A, || 42));
+    /// assert_eq!(42, *enum_map.get_or_insert_with(Letter::A, || 24));
+    /// ```
+    pub fn get_or_insert_with(&mut self, key: K, value_provider: impl FnOnce() -> V) -> &mut V {
+        self.data[key.position()].get_or_insert_with(value_provider)
+    }
+
+    /// Iterates over the variants holding a value, skipping the `None` ones.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated, Debug, PartialEq)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// enum_map.insert(Letter::B, 42);
+    /// let present: Vec<_> = enum_map.iter_some().collect();
+    /// assert_eq!(vec![(Letter::B, &42)], present);
+    /// ```
+    pub fn iter_some(&self) -> impl Iterator<Item = (K, &V)> {
+        self.iter_kv()
+            .filter_map(|(key, value)| value.as_ref().map(|value| (key, value)))
+    }
+
+    /// Mutably iterates over the variants holding a value, skipping the `None` ones. Only the values are mutable.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// enum_map.insert(Letter::B, 42);
+    /// enum_map.iter_some_mut().for_each(|(_letter, value)| *value += 1);
+    /// assert_eq!(None, enum_map[Letter::A]);
+    /// assert_eq!(Some(43), enum_map[Letter::B]);
+    /// ```
+    pub fn iter_some_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> {
+        self.iter_kv_mut()
+            .filter_map(|(key, value)| value.as_mut().map(|value| (key, value)))
+    }
+
+    /// Retains only the values the predicate returns `true` for, the rest is set to `None`.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// enum_map.insert(Letter::A, 1);
+    /// enum_map.insert(Letter::B, 2);
+    /// enum_map.retain(|_letter, value| *value % 2 == 0);
+    /// assert_eq!(None, enum_map[Letter::A]);
+    /// assert_eq!(Some(2), enum_map[Letter::B]);
+    /// ```
+    pub fn retain(&mut self, mut predicate: impl FnMut(&K, &mut V) -> bool) {
+        for (key, slot) in self.iter_kv_mut() {
+            if let Some(value) = slot
+                && !predicate(&key, value)
+            {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Takes all the values out of the map, leaving `None` for each variant.
+    /// Yields the variants holding a value along with the value.
+    ///
+    /// All the values are removed even if the iterator is not fully consumed.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated, Debug, PartialEq)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+    /// enum_map.insert(Letter::B, 42);
+    /// let drained: Vec<_> = enum_map.drain().collect();
+    /// assert_eq!(vec![(Letter::B, 42)], drained);
+    /// assert!(enum_map.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> impl Iterator<Item = (K, V)> {
+        let data = std::mem::replace(&mut self.data, array::from_fn(|_| None));
+        keys::<K>(N)
+            .zip(data)
+            .filter_map(|(key, value)| value.map(|value| (key, value)))
+    }
 }
 
 impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
@@ -345,6 +873,43 @@ impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
         }
     }
 
+    /// Value of the given key. Unlike indexing by value, the key is only borrowed.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let letter = Letter::B;
+    /// let enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::new(|| 42);
+    /// assert_eq!(&42, enum_map.get(&letter));
+    /// ```
+    pub fn get(&self, key: &K) -> &V {
+        &self.data[key.position()]
+    }
+
+    /// Mutable reference to the value of the given key. Unlike indexing by value, the key is only borrowed.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let letter = Letter::B;
+    /// let mut enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// *enum_map.get_mut(&letter) += 1;
+    /// assert_eq!(1, enum_map[letter]);
+    /// ```
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        &mut self.data[key.position()]
+    }
+
     /// Iterates over the EnumMap's key-value pairs.
     ///
     /// ```
@@ -361,9 +926,8 @@ impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
     /// }
     ///
     /// ```
-    #[cfg(feature = "variants")]
-    pub fn iter_kv(&self) -> std::iter::Zip<std::slice::Iter<'_, K>, std::slice::Iter<'_, V>> {
-        K::VARIANTS.iter().zip(self.data.iter())
+    pub fn iter_kv(&self) -> std::iter::Zip<Keys<K>, std::slice::Iter<'_, V>> {
+        keys::<K>(N).zip(self.data.iter())
     }
 
     /// Mutably iterates over the EnumMap's key-value pairs. Only the values are mutable.
@@ -384,11 +948,8 @@ impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
     /// }
     ///
     /// ```
-    #[cfg(feature = "variants")]
-    pub fn iter_kv_mut(
-        &mut self,
-    ) -> std::iter::Zip<std::slice::Iter<'_, K>, std::slice::IterMut<'_, V>> {
-        K::VARIANTS.iter().zip(self.data.iter_mut())
+    pub fn iter_kv_mut(&mut self) -> std::iter::Zip<Keys<K>, std::slice::IterMut<'_, V>> {
+        keys::<K>(N).zip(self.data.iter_mut())
     }
 
     /// Iterates over the EnumMap's values.
@@ -407,7 +968,6 @@ impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
     /// }
     ///
     /// ```
-    #[cfg(feature = "variants")]
     pub fn iter(&self) -> std::slice::Iter<'_, V> {
         self.data.iter()
     }
@@ -430,7 +990,6 @@ impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
     /// }
     ///
     /// ```
-    #[cfg(feature = "variants")]
     pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, V> {
         self.data.iter_mut()
     }
@@ -456,17 +1015,126 @@ impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
     /// assert_eq!(24, enum_map[Letter::B]);
     ///
     /// ```
-    #[cfg(feature = "variants")]
     pub fn new_inspect(mut default: impl FnMut(&K) -> V) -> Self {
-        let init_fn = |index| {
-            // Finds the enum variant by its index, as the array is sorted by discriminants in ascending order.
-            default(&K::VARIANTS[index])
-        };
+        let init_fn = |index| default(&K::from_position(index));
         Self {
             data: array::from_fn(init_fn),
             _key: PhantomData,
         }
     }
+
+    /// Creates a new EnumMap on the heap, the value of each variant being produced by the provided function.
+    /// The function receives the enum variant being initialized for inspection.
+    ///
+    /// Values are moved to the heap one by one, the whole map is never placed on the stack.
+    /// Values don't need to be `Clone`.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///   A,
+    ///   B,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Letter, String, { Letter::SIZE }>::new_boxed_inspect(|letter| {
+    ///    match letter {
+    ///       Letter::A => "a".to_owned(),
+    ///       Letter::B => "b".to_owned(),
+    ///    }
+    /// });
+    /// assert_eq!("b", enum_map[Letter::B]);
+    /// ```
+    pub fn new_boxed_inspect(mut default: impl FnMut(&K) -> V) -> Box<Self> {
+        boxed_from_fn(|position| default(&K::from_position(position)))
+    }
+}
+
+impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
+    /// Creates an EnumMap of values in the order of their variants' positions.
+    /// Usable in const context, e.g. to define lookup tables as constants.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///   A,
+    ///   B,
+    /// }
+    ///
+    /// const SCORES: EnumMap<Letter, u32, { Letter::SIZE }> = EnumMap::from_array([1, 3]);
+    /// assert_eq!(3, SCORES[Letter::B]);
+    /// ```
+    pub const fn from_array(data: [V; N]) -> Self {
+        Self {
+            data,
+            _key: PhantomData,
+        }
+    }
+
+    /// Value at the given position. Usable in const context, where keys are converted to positions
+    /// by the inherent `const fn const_position` generated by `#[derive(Enumerated)]` for enums annotated
+    /// with `#[enumerated(const_items)]`.
+    ///
+    /// There is no const lookup by key, [Enumerated::position] is a trait method and can't be called
+    /// in const context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is not lower than `N`, failing compilation when evaluated in const context.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated, Clone, Copy)]
+    /// #[enumerated(const_items)]
+    /// pub enum Letter {
+    ///   A,
+    ///   B,
+    /// }
+    ///
+    /// const SCORES: EnumMap<Letter, u32, { Letter::SIZE }> = EnumMap::from_array([1, 3]);
+    /// // A table derived from another one at compile time
+    /// const DOUBLED: EnumMap<Letter, u32, { Letter::SIZE }> = {
+    ///     let mut doubled = [0; Letter::SIZE];
+    ///     let mut position = 0;
+    ///     while position < Letter::SIZE {
+    ///         doubled[position] = *SCORES.get_by_position(position) * 2;
+    ///         position += 1;
+    ///     }
+    ///     EnumMap::from_array(doubled)
+    /// };
+    /// const B_DOUBLED: u32 = *DOUBLED.get_by_position(Letter::B.const_position());
+    /// assert_eq!(6, B_DOUBLED);
+    /// ```
+    pub const fn get_by_position(&self, position: usize) -> &V {
+        &self.data[position]
+    }
+
+    /// Mutable reference to the value at the given position. Usable in const context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is not lower than `N`, failing compilation when evaluated in const context.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated, Clone, Copy)]
+    /// #[enumerated(const_items)]
+    /// pub enum Letter {
+    ///   A,
+    ///   B,
+    /// }
+    ///
+    /// const SCORES: EnumMap<Letter, u32, { Letter::SIZE }> = {
+    ///     let mut scores = EnumMap::new_with_all(1);
+    ///     *scores.get_by_position_mut(Letter::B.const_position()) = 3;
+    ///     scores
+    /// };
+    /// assert_eq!(3, SCORES[Letter::B]);
+    /// ```
+    pub const fn get_by_position_mut(&mut self, position: usize) -> &mut V {
+        &mut self.data[position]
+    }
 }
 
 impl<K: Enumerated, V: Copy, const N: usize> EnumMap<K, V, N> {
@@ -540,6 +1208,77 @@ impl<K: Enumerated, V, const N: usize> IndexMut<K> for EnumMap<K, V, N> {
     }
 }
 
+/// Allows indexing of EnumMap by borrowed keys, for keys that aren't `Copy`.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated};
+///
+/// pub struct Slot(u8);
+///
+/// impl Enumerated for Slot {
+///     const SIZE: usize = 2;
+///     # #[cfg(feature = "variants")]
+///     # const VARIANTS: &'static [Self] = &[Slot(0), Slot(1)];
+///
+///     fn position(&self) -> usize {
+///         self.0 as usize
+///     }
+///
+///     fn from_position(position: usize) -> Self {
+///         assert!(position < Self::SIZE);
+///         Slot(position as u8)
+///     }
+/// }
+///
+/// let slot = Slot(1);
+/// let mut enum_map = EnumMap::<Slot, i32, { Slot::SIZE }>::new_default();
+/// enum_map[&slot] = 42;
+/// assert_eq!(42, enum_map[&slot]);
+/// assert_eq!(42, enum_map[slot]);
+/// ```
+impl<K: Enumerated, V, const N: usize> Index<&K> for EnumMap<K, V, N> {
+    type Output = V;
+
+    fn index(&self, key: &K) -> &Self::Output {
+        &self.data[key.position()]
+    }
+}
+
+/// Allows mutable indexing of EnumMap by borrowed keys, for keys that aren't `Copy`.
+impl<K: Enumerated, V, const N: usize> IndexMut<&K> for EnumMap<K, V, N> {
+    fn index_mut(&mut self, key: &K) -> &mut Self::Output {
+        &mut self.data[key.position()]
+    }
+}
+
+/// Collects key-value pairs into an EnumMap built directly on the heap. Keys not present are set to
+/// `V::default()`, the last value of a key repeated wins.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated};
+/// #[derive(Enumerated, Clone, Copy)]
+/// pub enum Letter {
+///    A,
+///    B,
+///    C,
+/// }
+///
+/// let enum_map: Box<EnumMap<Letter, u32, { Letter::SIZE }>> =
+///     [(Letter::A, 1), (Letter::C, 3)].into_iter().collect();
+/// assert_eq!(1, enum_map[Letter::A]);
+/// assert_eq!(0, enum_map[Letter::B]);
+/// assert_eq!(3, enum_map[Letter::C]);
+/// ```
+impl<K: Enumerated, V: Default, const N: usize> FromIterator<(K, V)> for Box<EnumMap<K, V, N>> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut enum_map = EnumMap::new_boxed_default();
+        for (key, value) in iter {
+            enum_map[key] = value;
+        }
+        enum_map
+    }
+}
+
 /// Implements Debug for EnumMap. EnumMap is printed as a map of enum variants to their values.
 ///
 /// ```
@@ -556,16 +1295,11 @@ impl<K: Enumerated, V, const N: usize> IndexMut<K> for EnumMap<K, V, N> {
 /// ```
 ///
 #[cfg(feature = "debug")]
-impl<K: Enumerated + Debug, V: Debug, const N: usize> std::fmt::Debug for EnumMap<K, V, N> {
+impl<K: Enumerated + std::fmt::Debug, V: std::fmt::Debug, const N: usize> std::fmt::Debug
+    for EnumMap<K, V, N>
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_map()
-            .entries(
-                K::VARIANTS
-                    .iter()
-                    .enumerate()
-                    .map(|(index, variant)| (variant, &self.data[index])),
-            )
-            .finish()
+        f.debug_map().entries(self.iter_kv()).finish()
     }
 }
 
@@ -667,6 +1401,7 @@ mod tests {
     /// No Debug derived on purpose, the crate must be usable without [std::fmt::Debug] derived
     /// for the enum.
     #[derive(Enumerated, Debug)]
+    #[enumerated(const_items)]
     pub(super) enum Letter {
         A,
         B,
@@ -691,6 +1426,37 @@ mod tests {
         assert_eq!(Some(42), enum_map[Letter::A]);
     }
 
+    #[test]
+    fn option_map_operations() {
+        let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+        assert!(enum_map.is_empty());
+        assert_eq!(None, enum_map.insert(Letter::A, 1));
+        assert_eq!(Some(1), enum_map.insert(Letter::A, 2));
+        assert_eq!(1, enum_map.len());
+        assert!(enum_map.contains_key(&Letter::A));
+        assert!(!enum_map.contains_key(&Letter::B));
+
+        *enum_map.entry(Letter::B).or_default() += 5;
+        *enum_map.entry(Letter::B).or_default() += 5;
+        assert_eq!(Some(10), enum_map[Letter::B]);
+        assert_eq!(2, enum_map.len());
+
+        assert_eq!(Some(2), enum_map.remove(&Letter::A));
+        assert_eq!(None, enum_map.remove(&Letter::A));
+        assert_eq!(1, enum_map.len());
+    }
+
+    #[test]
+    fn option_map_drain_partially_consumed() {
+        let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+        enum_map.insert(Letter::A, 1);
+        enum_map.insert(Letter::B, 2);
+        let mut drain = enum_map.drain();
+        assert_eq!(Some(1), drain.next().map(|(_, value)| value));
+        drop(drain);
+        assert!(enum_map.is_empty());
+    }
+
     #[test]
     fn non_default_type() {
         #[derive(PartialEq, Eq, Debug)]
@@ -700,6 +1466,110 @@ mod tests {
         assert_eq!(NonDefault, enum_map[Letter::B]);
     }
 
+    #[test]
+    fn borrowed_keys() {
+        // Neither `Copy` nor `Clone`
+        struct Register(&'static str);
+
+        impl Enumerated for Register {
+            const SIZE: usize = 2;
+            #[cfg(feature = "variants")]
+            const VARIANTS: &'static [Self] = &[Register("ax"), Register("bx")];
+
+            fn position(&self) -> usize {
+                match self.0 {
+                    "ax" => 0,
+                    "bx" => 1,
+                    other => panic!("Unknown register {}", other),
+                }
+            }
+
+            fn from_position(position: usize) -> Self {
+                Register(["ax", "bx"][position])
+            }
+        }
+
+        crate::check_enumerated::<Register>();
+
+        let register = Register("bx");
+        let mut enum_map = EnumMap::<Register, u32, { Register::SIZE }>::new_default();
+        enum_map[&register] = 1;
+        *enum_map.get_mut(&register) += 1;
+        assert_eq!(2, enum_map[&register]);
+        assert_eq!(&2, enum_map.get(&register));
+        assert_eq!(0, *enum_map.get(&Register("ax")));
+        assert_eq!(2, enum_map[register]);
+    }
+
+    #[test]
+    fn const_tables() {
+        const SCORES: EnumMap<Letter, u32, { Letter::SIZE }> = EnumMap::from_array([2, 5]);
+        const TOTAL: u32 = {
+            let mut total = 0;
+            let mut index = 0;
+            while index < Letter::ALL.len() {
+                total += *SCORES.get_by_position(index);
+                index += 1;
+            }
+            total
+        };
+        const INVERTED: EnumMap<Letter, u32, { Letter::SIZE }> = {
+            let mut inverted = SCORES;
+            *inverted.get_by_position_mut(Letter::A.const_position()) = *SCORES.get_by_position(1);
+            *inverted.get_by_position_mut(Letter::B.const_position()) = *SCORES.get_by_position(0);
+            inverted
+        };
+        assert_eq!(7, TOTAL);
+        assert_eq!(5, INVERTED[Letter::A]);
+        assert_eq!(2, INVERTED[Letter::B]);
+    }
+
+    /// Maps far larger than the thread's stack are constructed on the heap without overflowing it.
+    #[test]
+    fn boxed_construction_small_stack() {
+        #[derive(Enumerated, Clone, Copy)]
+        enum Digit {
+            D0,
+            D1,
+            D2,
+            D3,
+            D4,
+            D5,
+            D6,
+            D7,
+            D8,
+            D9,
+        }
+
+        #[derive(Enumerated, Clone, Copy)]
+        struct Code(Digit, Digit, Digit, Digit);
+
+        // 10 000 values of 128 bytes, about 1.3 MB per map
+        type Large = EnumMap<Code, [u64; 16], { Code::SIZE }>;
+
+        std::thread::Builder::new()
+            .stack_size(128 * 1024)
+            .spawn(|| {
+                let mut enum_map = Large::new_boxed_default();
+                let code = Code(Digit::D1, Digit::D2, Digit::D3, Digit::D4);
+                enum_map[code] = [1; 16];
+                assert_eq!([1; 16], enum_map[code]);
+
+                let enum_map = Large::new_boxed_inspect(|code| [code.position() as u64; 16]);
+                assert_eq!([1234; 16], enum_map[code]);
+
+                let enum_map: Box<Large> = [(code, [7; 16])].into_iter().collect();
+                assert_eq!([7; 16], enum_map[code]);
+                assert_eq!(
+                    [0; 16],
+                    enum_map[Code(Digit::D0, Digit::D0, Digit::D0, Digit::D0)]
+                );
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
     /// Safeguard test. Nothing inside the EnumMap should prevent from moving it to the heap.
     #[test]
     fn heap_allocation() {
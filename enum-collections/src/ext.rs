@@ -1,19 +1,416 @@
 //! Implementations of the [Enumerated] trait for common data types.
+//!
+//! Generic types, `Option<T>`, `Result<T, E>`, tuples and arrays, build their `VARIANTS` at compile time
+//! from the `VARIANTS` of their components. Stable Rust can't size an array by a generic parameter, the variants
+//! are written into a buffer of the smallest of fixed capacities that fits them, up to 16 MiB.
 
-use crate::Enumerated;
+use crate::{
+    Enumerated,
+    enumerated::{combine_layout_hash, layout_hash},
+};
+use std::cmp::Ordering;
+
+#[cfg(feature = "variants")]
+use table::{VariantsTable, variant_at};
+
+/// `VARIANTS` of generic types, built in const context.
+#[cfg(feature = "variants")]
+mod table {
+    use crate::Enumerated;
+    use std::{marker::PhantomData, mem::MaybeUninit};
+
+    /// Holds the `VARIANTS` of the generic type `S`, each type defining its own by `variants_table!`.
+    pub(super) struct VariantsTable<S>(PhantomData<S>);
+
+    /// Unit of the buffers holding the variants, aligned for the `Enumerated` types of this crate.
+    pub(super) type Word = MaybeUninit<u128>;
+
+    /// Variant of `T` at the given position, copied out of `T::VARIANTS`.
+    pub(super) const fn variant_at<T: Enumerated>(position: usize) -> T {
+        // SAFETY: A bitwise copy of a const's value, as each use of a const is. Copies end up in `VARIANTS`
+        // of another type, behind a `&'static` reference, and are never dropped.
+        unsafe { std::ptr::read(&T::VARIANTS[position]) }
+    }
+
+    /// Views the first `size` values of type `S` written to the buffer as a slice.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must hold `size` initialized values of type `S` and be aligned for `S`.
+    pub(super) const unsafe fn as_variants<S>(
+        buffer: &'static [Word],
+        size: usize,
+    ) -> &'static [S] {
+        // SAFETY: Guaranteed by the caller.
+        unsafe { &*std::ptr::slice_from_raw_parts(buffer.as_ptr().cast::<S>(), size) }
+    }
+}
+
+/// Defines `VARIANTS` of `$self` in an inherent impl of `VariantsTable`, the variant at each position
+/// being `$variant`. A buffer of words is interned rather than `[$self; SIZE]`: its type doesn't depend
+/// on `$self`, a `&'static` borrow of it is accepted even if `$self` might contain interior mutability.
+#[cfg(feature = "variants")]
+macro_rules! variants_table {
+    ($self:ty, |$position:ident| $variant:expr) => {
+        const fn variant($position: usize) -> $self {
+            $variant
+        }
+
+        const fn buffer<const CAPACITY: usize>() -> [table::Word; CAPACITY] {
+            assert!(
+                align_of::<$self>() <= align_of::<table::Word>(),
+                concat!(
+                    "`",
+                    stringify!($self),
+                    "` is aligned beyond the `VARIANTS` buffer"
+                )
+            );
+            let mut buffer = [const { table::Word::uninit() }; CAPACITY];
+            let values = buffer.as_mut_ptr().cast::<$self>();
+            let mut position = 0;
+            while position < <$self as Enumerated>::SIZE {
+                // SAFETY: The capacity fits `SIZE` values, aligned as asserted above.
+                unsafe { values.add(position).write(Self::variant(position)) };
+                position += 1;
+            }
+            buffer
+        }
+
+        const BUFFER: &'static [table::Word] = {
+            let words = (<$self as Enumerated>::SIZE * size_of::<$self>())
+                .div_ceil(size_of::<table::Word>());
+            // Capacities grow by a factor of four, the buffer is never more than four times larger than needed
+            if words <= 4 {
+                &Self::buffer::<4>()
+            } else if words <= 16 {
+                &Self::buffer::<16>()
+            } else if words <= 64 {
+                &Self::buffer::<64>()
+            } else if words <= 256 {
+                &Self::buffer::<256>()
+            } else if words <= 1024 {
+                &Self::buffer::<1024>()
+            } else if words <= 4096 {
+                &Self::buffer::<4096>()
+            } else if words <= 16384 {
+                &Self::buffer::<16384>()
+            } else if words <= 65536 {
+                &Self::buffer::<65536>()
+            } else if words <= 262144 {
+                &Self::buffer::<262144>()
+            } else if words <= 1048576 {
+                &Self::buffer::<1048576>()
+            } else {
+                panic!(concat!(
+                    "`VARIANTS` of `",
+                    stringify!($self),
+                    "` exceed 16 MiB"
+                ))
+            }
+        };
+
+        // SAFETY: The buffer holds `SIZE` values written by `buffer`.
+        const VARIANTS: &'static [$self] =
+            unsafe { table::as_variants(Self::BUFFER, <$self as Enumerated>::SIZE) };
+    };
+}
 
 impl Enumerated for bool {
     const SIZE: usize = 2;
+    #[cfg(feature = "variants")]
     const VARIANTS: &'static [Self] = &[false, true];
+    const LAYOUT_HASH: u64 = layout_hash(&["false", "true"]);
+
+    fn position(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_position(position: usize) -> Self {
+        match position {
+            0 => false,
+            1 => true,
+            _ => panic!("Position {} is out of range of `bool`", position),
+        }
+    }
+}
+
+impl Enumerated for () {
+    const SIZE: usize = 1;
+    #[cfg(feature = "variants")]
+    const VARIANTS: &'static [Self] = &[()];
+    const LAYOUT_HASH: u64 = layout_hash(&["()"]);
+
+    fn position(&self) -> usize {
+        0
+    }
+
+    fn from_position(position: usize) -> Self {
+        assert!(
+            position == 0,
+            "Position {} is out of range of `()`",
+            position
+        );
+    }
+}
+
+/// Variants are sorted as `Less`, `Equal`, `Greater`, the same as their discriminants.
+impl Enumerated for Ordering {
+    const SIZE: usize = 3;
+    #[cfg(feature = "variants")]
+    const VARIANTS: &'static [Self] = &[Ordering::Less, Ordering::Equal, Ordering::Greater];
+    const VARIANT_NAMES: &'static [&'static str] = &["Less", "Equal", "Greater"];
+    const LAYOUT_HASH: u64 = layout_hash(Self::VARIANT_NAMES);
+
+    fn position(&self) -> usize {
+        (*self as i8 + 1) as usize
+    }
+
+    fn from_position(position: usize) -> Self {
+        match position {
+            0 => Ordering::Less,
+            1 => Ordering::Equal,
+            2 => Ordering::Greater,
+            _ => panic!("Position {} is out of range of `Ordering`", position),
+        }
+    }
+}
+
+#[cfg(feature = "variants")]
+const U8_VARIANTS: [u8; 256] = {
+    let mut variants = [0; 256];
+    let mut index = 0;
+    while index < variants.len() {
+        variants[index] = index as u8;
+        index += 1;
+    }
+    variants
+};
+
+impl Enumerated for u8 {
+    const SIZE: usize = 256;
+    #[cfg(feature = "variants")]
+    const VARIANTS: &'static [Self] = &U8_VARIANTS;
+    const LAYOUT_HASH: u64 = layout_hash(&["u8"]);
 
-    fn position(self) -> usize {
-        self as usize
+    fn position(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_position(position: usize) -> Self {
+        u8::try_from(position)
+            .unwrap_or_else(|_| panic!("Position {} is out of range of `u8`", position))
+    }
+}
+
+#[cfg(feature = "variants")]
+const I8_VARIANTS: [i8; 256] = {
+    let mut variants = [0; 256];
+    let mut index = 0;
+    while index < variants.len() {
+        variants[index] = (index as i16 + i8::MIN as i16) as i8;
+        index += 1;
+    }
+    variants
+};
+
+/// Values are positioned in ascending order, `i8::MIN` being the first one.
+impl Enumerated for i8 {
+    const SIZE: usize = 256;
+    #[cfg(feature = "variants")]
+    const VARIANTS: &'static [Self] = &I8_VARIANTS;
+    const LAYOUT_HASH: u64 = layout_hash(&["i8"]);
+
+    fn position(&self) -> usize {
+        (*self as i16 - i8::MIN as i16) as usize
+    }
+
+    fn from_position(position: usize) -> Self {
+        assert!(
+            position < Self::SIZE,
+            "Position {} is out of range of `i8`",
+            position
+        );
+        (position as i16 + i8::MIN as i16) as i8
+    }
+}
+
+#[cfg(feature = "variants")]
+impl<T: Enumerated> VariantsTable<Option<T>> {
+    variants_table!(Option<T>, |position| match position {
+        0 => None,
+        position => Some(variant_at::<T>(position - 1)),
+    });
+}
+
+/// `None` is positioned first, followed by `Some` of each of `T`'s variants.
+impl<T: Enumerated> Enumerated for Option<T> {
+    const SIZE: usize = T::SIZE + 1;
+    #[cfg(feature = "variants")]
+    const VARIANTS: &'static [Self] = VariantsTable::<Self>::VARIANTS;
+    const LAYOUT_HASH: u64 = combine_layout_hash(layout_hash(&["None", "Some"]), T::LAYOUT_HASH);
+
+    fn position(&self) -> usize {
+        match self {
+            None => 0,
+            Some(value) => value.position() + 1,
+        }
+    }
+
+    fn from_position(position: usize) -> Self {
+        match position {
+            0 => None,
+            position => Some(T::from_position(position - 1)),
+        }
+    }
+}
+
+#[cfg(feature = "variants")]
+impl<T: Enumerated, E: Enumerated> VariantsTable<Result<T, E>> {
+    variants_table!(Result<T, E>, |position| if position < T::SIZE {
+        Ok(variant_at::<T>(position))
+    } else {
+        Err(variant_at::<E>(position - T::SIZE))
+    });
+}
+
+/// `Ok` of each of `T`'s variants is positioned first, followed by `Err` of each of `E`'s variants.
+impl<T: Enumerated, E: Enumerated> Enumerated for Result<T, E> {
+    const SIZE: usize = T::SIZE + E::SIZE;
+    #[cfg(feature = "variants")]
+    const VARIANTS: &'static [Self] = VariantsTable::<Self>::VARIANTS;
+    const LAYOUT_HASH: u64 = combine_layout_hash(
+        combine_layout_hash(layout_hash(&["Ok", "Err"]), T::LAYOUT_HASH),
+        E::LAYOUT_HASH,
+    );
+
+    fn position(&self) -> usize {
+        match self {
+            Ok(value) => value.position(),
+            Err(error) => T::SIZE + error.position(),
+        }
+    }
+
+    fn from_position(position: usize) -> Self {
+        if position < T::SIZE {
+            Ok(T::from_position(position))
+        } else {
+            Err(E::from_position(position - T::SIZE))
+        }
+    }
+}
+
+/// Implements [Enumerated] for a tuple of [Enumerated] types. Variants are positioned in
+/// lexicographic order, the first element being the most significant one.
+macro_rules! impl_enumerated_tuple {
+    ($($element:ident $value:ident),+) => {
+        #[cfg(feature = "variants")]
+        impl<$($element: Enumerated),+> VariantsTable<($($element,)+)> {
+            variants_table!(($($element,)+), |position| {
+                // Size of the elements following the one being resolved
+                let mut stride = <($($element,)+) as Enumerated>::SIZE;
+                ($({
+                    stride /= $element::SIZE;
+                    variant_at::<$element>(position / stride % $element::SIZE)
+                },)+)
+            });
+        }
+
+        impl<$($element: Enumerated),+> Enumerated for ($($element,)+) {
+            const SIZE: usize = 1 $(* $element::SIZE)+;
+            #[cfg(feature = "variants")]
+            const VARIANTS: &'static [Self] = VariantsTable::<Self>::VARIANTS;
+            const LAYOUT_HASH: u64 = {
+                let hash = layout_hash(&["(..)"]);
+                $(let hash = combine_layout_hash(hash, $element::LAYOUT_HASH);)+
+                hash
+            };
+
+            fn position(&self) -> usize {
+                let ($($value,)+) = self;
+                let mut position = 0;
+                $(position = position * $element::SIZE + $value.position();)+
+                position
+            }
+
+            fn from_position(position: usize) -> Self {
+                assert!(position < Self::SIZE, "Position {} is out of range of a tuple", position);
+                // Size of the elements following the one being resolved
+                let mut stride = Self::SIZE;
+                ($({
+                    stride /= $element::SIZE;
+                    $element::from_position(position / stride % $element::SIZE)
+                },)+)
+            }
+        }
+    };
+}
+
+impl_enumerated_tuple!(A a);
+impl_enumerated_tuple!(A a, B b);
+impl_enumerated_tuple!(A a, B b, C c);
+impl_enumerated_tuple!(A a, B b, C c, D d);
+impl_enumerated_tuple!(A a, B b, C c, D d, E e);
+impl_enumerated_tuple!(A a, B b, C c, D d, E e, F f);
+
+#[cfg(feature = "variants")]
+impl<T: Enumerated, const M: usize> VariantsTable<[T; M]> {
+    variants_table!([T; M], |position| {
+        let mut elements = [const { std::mem::MaybeUninit::<T>::uninit() }; M];
+        let mut index = 0;
+        while index < M {
+            let stride = T::SIZE.pow((M - index - 1) as u32);
+            elements[index].write(variant_at::<T>(position / stride % T::SIZE));
+            index += 1;
+        }
+        // SAFETY: Each of the M elements is written above, `MaybeUninit<T>` has the same layout as `T`.
+        unsafe { elements.as_ptr().cast::<[T; M]>().read() }
+    });
+}
+
+/// Variants are positioned in lexicographic order, the first element being the most significant one.
+impl<T: Enumerated, const M: usize> Enumerated for [T; M] {
+    const SIZE: usize = T::SIZE.pow(M as u32);
+    #[cfg(feature = "variants")]
+    const VARIANTS: &'static [Self] = VariantsTable::<Self>::VARIANTS;
+    const LAYOUT_HASH: u64 = {
+        let mut hash = layout_hash(&["[..]"]);
+        let mut element = 0;
+        while element < M {
+            hash = combine_layout_hash(hash, T::LAYOUT_HASH);
+            element += 1;
+        }
+        hash
+    };
+
+    fn position(&self) -> usize {
+        self.iter().fold(0, |position, element| {
+            position * T::SIZE + element.position()
+        })
+    }
+
+    fn from_position(position: usize) -> Self {
+        assert!(
+            position < Self::SIZE,
+            "Position {} is out of range of an array",
+            position
+        );
+        std::array::from_fn(|index| {
+            let stride = T::SIZE.pow((M - index - 1) as u32);
+            T::from_position(position / stride % T::SIZE)
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{EnumMap, Enumerated, em_default};
+    use crate::{EnumMap, Enumerated, em, em_default};
+    use std::cmp::Ordering;
+
+    #[derive(Enumerated, Clone, Copy)]
+    enum Status {
+        Up,
+        Down,
+    }
 
     #[test]
     fn test_bool() {
@@ -30,8 +427,238 @@ mod tests {
         assert_eq!(24, map[true]);
 
         let map = em_default!(bool, i32,);
-        for variant in bool::VARIANTS {
-            assert_eq!(0, map[*variant]);
+        for (variant, value) in map.iter_kv() {
+            assert_eq!(variant, bool::from_position(variant.position()));
+            assert_eq!(0, *value);
+        }
+    }
+
+    #[test]
+    fn test_unit() {
+        assert_eq!(0, ().position());
+        assert_eq!(1, <()>::SIZE);
+
+        let mut map = EnumMap::<(), i32, { <()>::SIZE }>::new_default();
+        map[()] = 42;
+        assert_eq!(42, map[()]);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert_eq!(3, Ordering::SIZE);
+        for position in 0..Ordering::SIZE {
+            assert_eq!(position, Ordering::from_position(position).position());
+        }
+
+        let mut map = EnumMap::<Ordering, usize, { Ordering::SIZE }>::new_default();
+        for (left, right) in [(1, 2), (2, 2), (3, 2), (4, 2)] {
+            map[i32::cmp(&left, &right)] += 1;
+        }
+        assert_eq!(1, map[Ordering::Less]);
+        assert_eq!(1, map[Ordering::Equal]);
+        assert_eq!(2, map[Ordering::Greater]);
+
+        let signs = em!(Ordering, i8, Less => -1, Equal => 0, Greater => 1);
+        assert_eq!(-1, signs[Ordering::Less]);
+    }
+
+    #[test]
+    fn test_contract() {
+        use crate::check_enumerated;
+
+        check_enumerated::<bool>();
+        check_enumerated::<()>();
+        check_enumerated::<Ordering>();
+        check_enumerated::<u8>();
+        check_enumerated::<i8>();
+        check_enumerated::<Option<bool>>();
+        check_enumerated::<Option<Option<Ordering>>>();
+        check_enumerated::<Result<bool, Ordering>>();
+        check_enumerated::<Result<(), u8>>();
+        check_enumerated::<(bool, Ordering)>();
+        check_enumerated::<(bool, Ordering, Option<bool>, (), u8)>();
+        check_enumerated::<[bool; 3]>();
+        check_enumerated::<[Ordering; 0]>();
+        check_enumerated::<(u8, u8)>();
+    }
+
+    #[test]
+    fn test_u8() {
+        assert_eq!(256, u8::SIZE);
+        for position in 0..u8::SIZE {
+            assert_eq!(position, u8::from_position(position).position());
+        }
+
+        // Byte-frequency table
+        let mut frequencies = EnumMap::<u8, usize, { u8::SIZE }>::new_default();
+        for byte in b"hello" {
+            frequencies[*byte] += 1;
+        }
+        assert_eq!(2, frequencies[b'l']);
+        assert_eq!(1, frequencies[b'o']);
+        assert_eq!(0, frequencies[b'x']);
+    }
+
+    #[test]
+    fn test_i8() {
+        assert_eq!(256, i8::SIZE);
+        assert_eq!(0, i8::MIN.position());
+        assert_eq!(128, 0i8.position());
+        assert_eq!(255, i8::MAX.position());
+        for position in 0..i8::SIZE {
+            assert_eq!(position, i8::from_position(position).position());
+        }
+    }
+
+    #[cfg(feature = "variants")]
+    #[test]
+    fn test_variants() {
+        assert_eq!(&[false, true], bool::VARIANTS);
+        assert_eq!(&[()], <()>::VARIANTS);
+        assert_eq!(
+            &[Ordering::Less, Ordering::Equal, Ordering::Greater],
+            Ordering::VARIANTS
+        );
+        assert_eq!(u8::SIZE, u8::VARIANTS.len());
+        assert!(u8::VARIANTS.is_sorted());
+        assert_eq!(i8::SIZE, i8::VARIANTS.len());
+        assert!(i8::VARIANTS.is_sorted());
+    }
+
+    /// Generic types build their variants out of their components' variants
+    #[cfg(feature = "variants")]
+    #[test]
+    fn test_generic_variants() {
+        assert_eq!(&[None, Some(false), Some(true)], Option::<bool>::VARIANTS);
+        assert_eq!(
+            &[Ok(false), Ok(true), Err(())],
+            Result::<bool, ()>::VARIANTS
+        );
+        assert_eq!(&[(false, ()), (true, ())], <(bool, ())>::VARIANTS);
+        assert_eq!(
+            &[[false, false], [false, true], [true, false], [true, true]],
+            <[bool; 2]>::VARIANTS
+        );
+        assert_eq!(&[[false; 0]], <[bool; 0]>::VARIANTS);
+        assert_eq!(65536, <(u8, u8)>::VARIANTS.len());
+        assert_eq!((1, 255), <(u8, u8)>::VARIANTS[511]);
+
+        // Components that are not `Copy`
+        #[derive(Enumerated, Debug, PartialEq)]
+        enum Signal {
+            Stop,
+            Go,
         }
+        assert_eq!(
+            &[
+                (Signal::Stop, false),
+                (Signal::Stop, true),
+                (Signal::Go, false),
+                (Signal::Go, true)
+            ],
+            <(Signal, bool)>::VARIANTS
+        );
+        crate::check_enumerated::<Option<Signal>>();
+    }
+
+    #[test]
+    fn test_option() {
+        assert_eq!(3, Option::<Status>::SIZE);
+        assert_eq!(0, None::<Status>.position());
+        assert_eq!(1, Some(Status::Up).position());
+        assert_eq!(2, Some(Status::Down).position());
+
+        let mut map = EnumMap::<Option<Status>, i32, { Option::<Status>::SIZE }>::new_default();
+        map[None] = 1;
+        map[Some(Status::Down)] = 2;
+        assert_eq!(1, map[None]);
+        assert_eq!(0, map[Some(Status::Up)]);
+        assert_eq!(2, map[Some(Status::Down)]);
+
+        for position in 0..Option::<Status>::SIZE {
+            assert_eq!(
+                position,
+                Option::<Status>::from_position(position).position()
+            );
+        }
+        assert_eq!(3, map.iter_kv().count());
+    }
+
+    #[test]
+    fn test_result() {
+        assert_eq!(4, Result::<Status, bool>::SIZE);
+        assert_eq!(0, Ok::<Status, bool>(Status::Up).position());
+        assert_eq!(1, Ok::<Status, bool>(Status::Down).position());
+        assert_eq!(2, Err::<Status, bool>(false).position());
+        assert_eq!(3, Err::<Status, bool>(true).position());
+
+        let mut map =
+            EnumMap::<Result<Status, bool>, i32, { Result::<Status, bool>::SIZE }>::new_default();
+        map[Err(true)] = 42;
+        assert_eq!(42, map[Err(true)]);
+        assert_eq!(0, map[Ok(Status::Up)]);
+
+        for position in 0..Result::<Status, bool>::SIZE {
+            assert_eq!(
+                position,
+                Result::<Status, bool>::from_position(position).position()
+            );
+        }
+    }
+
+    #[derive(Enumerated, Clone, Copy)]
+    enum Speed {
+        Slow,
+        Medium,
+        Fast,
+    }
+
+    #[test]
+    fn test_tuple() {
+        assert_eq!(6, <(Status, Speed)>::SIZE);
+        assert_eq!(0, (Status::Up, Speed::Slow).position());
+        assert_eq!(2, (Status::Up, Speed::Fast).position());
+        assert_eq!(3, (Status::Down, Speed::Slow).position());
+        assert_eq!(5, (Status::Down, Speed::Fast).position());
+        assert_eq!(12, <(Status, Speed, bool)>::SIZE);
+        assert_eq!(11, (Status::Down, Speed::Fast, true).position());
+        assert_eq!(8192, <(bool, bool, bool, bool, bool, u8)>::SIZE);
+
+        let mut map = EnumMap::<(Status, Speed), i32, { <(Status, Speed)>::SIZE }>::new_default();
+        map[(Status::Down, Speed::Medium)] = 42;
+        assert_eq!(42, map[(Status::Down, Speed::Medium)]);
+        assert_eq!(0, map[(Status::Up, Speed::Medium)]);
+
+        for position in 0..<(Status, Speed, bool)>::SIZE {
+            assert_eq!(
+                position,
+                <(Status, Speed, bool)>::from_position(position).position()
+            );
+        }
+    }
+
+    #[test]
+    fn test_array() {
+        assert_eq!(8, <[bool; 3]>::SIZE);
+        assert_eq!(1, <[bool; 0]>::SIZE);
+        assert_eq!(0, [false, false, false].position());
+        assert_eq!(1, [false, false, true].position());
+        assert_eq!(4, [true, false, false].position());
+        assert_eq!(7, [true, true, true].position());
+        assert_eq!(
+            (Speed::Fast, Speed::Medium).position(),
+            [Speed::Fast, Speed::Medium].position()
+        );
+
+        for position in 0..<[Speed; 3]>::SIZE {
+            assert_eq!(position, <[Speed; 3]>::from_position(position).position());
+        }
+        assert_eq!([(); 0], <[(); 0]>::from_position(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_from_position_out_of_range() {
+        u8::from_position(256);
     }
 }
@@ -3,10 +3,10 @@
 //! See [EnumMap] for usage details.
 //!
 //! A map of enum variants to values. EnumMap is a fixed-size map, where each variant of the enum is mapped to a value.
-//! This implementation of EnumMap uses **safe Rust** only and is a a zero-cost abstraction over an array (**const-sized**),
+//! This implementation of EnumMap uses **safe Rust** by default; boxed construction and the optional `bytemuck`, `rkyv` and `ext` with `variants` integrations use audited `unsafe`, each block stating its safety argument. It is a zero-cost abstraction over an array (**const-sized**),
 //! where the index of the array corresponds to the position of the variant in the enum.
 //!
-//! Because it is a thin wrapper over an array, it is stack-allocated by default. Simply `std::boxed::Box`ing it will move it to the heap, at the caller's discretion.
+//! Because it is a thin wrapper over an array, it is stack-allocated by default. Simply `std::boxed::Box`ing it will move it to the heap, at the caller's discretion. Maps too large for the stack are built directly on the heap with `new_boxed_default`, `new_boxed_inspect` or by collecting key-value pairs into a `Box<EnumMap>`.
 //!- Indexed by enum variants.
 //!- IndexMut by enum variants.
 //!- Debug if the enum is Debug.
@@ -14,16 +14,73 @@
 //!
 //!Debug and Eq are optional features. They are enabled by default.
 //!
+//! For concurrent access, [EnumLockMap] guards each variant's value with its own lock.
 //!
+//!
+// Lets code generated by `#[derive(Enumerated)]` refer to `::enum_collections` within this crate as well
+extern crate self as enum_collections;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "bincode")]
+mod bincode;
+mod bitmap;
+#[cfg(feature = "borsh")]
+mod borsh;
+mod bounded;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+mod entry;
 mod enumerated;
 mod enummap;
 #[cfg(feature = "ext")]
 mod ext;
+mod lockmap;
+mod navigation;
+mod optionmap;
+#[cfg(feature = "proptest")]
+mod proptest;
+#[cfg(feature = "rand")]
+mod rand;
+#[cfg(feature = "rayon")]
+mod rayon;
+mod remote;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "schemars")]
+mod schemars;
 #[cfg(feature = "serde")]
 mod serde;
+mod sparse;
+#[cfg(feature = "zerocopy")]
+mod zerocopy;
 
-pub use crate::enumerated::Enumerated;
-pub use crate::enummap::EnumMap;
+#[cfg(feature = "arbitrary")]
+pub use crate::arbitrary::arbitrary_variant;
+pub use crate::bounded::{Bounded, OutOfRange};
+pub use crate::entry::{Entry, OccupiedEntry, VacantEntry};
+#[doc(hidden)]
+pub use crate::enumerated::combine_layout_hash as __combine_layout_hash;
+#[doc(hidden)]
+pub use crate::enumerated::layout_hash as __layout_hash;
+pub use crate::enumerated::{Enumerated, check_enumerated};
+#[doc(hidden)]
+pub use crate::enummap::__check_em_keys;
+pub use crate::enummap::{EnumMap, Keys};
+pub use crate::lockmap::EnumLockMap;
+pub use crate::navigation::Navigable;
+pub use crate::optionmap::EnumOptionMap;
+#[cfg(feature = "proptest")]
+pub use crate::proptest::{VariantStrategy, any_variant};
+#[cfg(feature = "rand")]
+pub use crate::rand::{UniformVariant, WeightError, WeightedVariant};
+#[cfg(feature = "rayon")]
+pub use crate::rayon::ParKeys;
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv::ArchivedEnumMap;
+#[cfg(feature = "serde")]
+pub use crate::serde::EnumMapWithUnknown;
+pub use crate::sparse::SparseEnumMap;
 pub use enum_collections_macros::Enumerated;
 
 #[cfg(test)]
@@ -41,5 +98,277 @@ mod tests {
         assert_eq!(0, Letter::A.position());
         assert_eq!(1, Letter::B.position());
         assert_eq!(2, Letter::SIZE);
+        assert!(matches!(Letter::from_position(0), Letter::A));
+        assert!(matches!(Letter::from_position(1), Letter::B));
+        assert_eq!(&["A", "B"], Letter::VARIANT_NAMES);
+    }
+
+    #[test]
+    #[should_panic(expected = "Position 2 is out of range of `Letter`")]
+    fn test_derive_macro_from_position_out_of_range() {
+        #[derive(Enumerated)]
+        enum Letter {
+            A,
+            B,
+        }
+
+        Letter::from_position(2);
+    }
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Direction {
+        North,
+        South,
+    }
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Speed {
+        Slow,
+        Medium,
+        Fast,
+    }
+
+    #[test]
+    fn test_derive_struct() {
+        #[derive(Enumerated, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Movement {
+            direction: Direction,
+            speed: Speed,
+        }
+
+        assert_eq!(6, Movement::SIZE);
+        let movement = Movement {
+            direction: Direction::South,
+            speed: Speed::Medium,
+        };
+        assert_eq!(4, movement.position());
+
+        let map = crate::EnumMap::<Movement, i32, { Movement::SIZE }>::new_inspect(|movement| {
+            movement.position() as i32
+        });
+        assert_eq!(4, map[movement]);
+
+        for position in 0..Movement::SIZE {
+            assert_eq!(position, Movement::from_position(position).position());
+        }
+        assert_eq!(movement, Movement::from_position(4));
+    }
+
+    #[test]
+    fn test_derive_tuple_struct() {
+        #[derive(Enumerated, Clone, Copy)]
+        struct Movement(Direction, Speed, Direction);
+
+        assert_eq!(12, Movement::SIZE);
+        assert_eq!(
+            0,
+            Movement(Direction::North, Speed::Slow, Direction::North).position()
+        );
+        assert_eq!(
+            11,
+            Movement(Direction::South, Speed::Fast, Direction::South).position()
+        );
+        for position in 0..Movement::SIZE {
+            assert_eq!(position, Movement::from_position(position).position());
+        }
+    }
+
+    #[test]
+    fn test_derive_unit_struct() {
+        #[derive(Enumerated, Clone, Copy)]
+        struct Unit;
+
+        assert_eq!(1, Unit::SIZE);
+        assert_eq!(0, Unit.position());
+    }
+
+    #[test]
+    fn test_derive_const_items() {
+        #[derive(Enumerated, Debug, PartialEq)]
+        #[enumerated(const_items)]
+        enum Letter {
+            A,
+            B,
+            C,
+        }
+
+        const B_POSITION: usize = Letter::B.const_position();
+        assert_eq!(1, B_POSITION);
+        assert_eq!([Letter::A, Letter::B, Letter::C], Letter::ALL);
+        for (position, letter) in Letter::ALL.into_iter().enumerate() {
+            assert_eq!(position, Enumerated::position(&letter));
+        }
+    }
+
+    /// The const position doesn't shadow the trait's, keys that aren't `Copy` are borrowed rather than moved
+    #[test]
+    fn test_derive_const_items_borrow_keys() {
+        #[derive(Enumerated, Debug, PartialEq)]
+        #[enumerated(const_items)]
+        enum Letter {
+            A,
+            B,
+        }
+
+        const B_POSITION: usize = Letter::B.const_position();
+        let key = Letter::B;
+        assert_eq!(B_POSITION, key.position());
+        assert_eq!(1, key.const_position());
+        assert_eq!(Letter::B, key);
+    }
+
+    /// Inherent items of the enum don't clash with the derive, unless `const_items` are requested
+    #[test]
+    fn test_derive_own_inherent_items() {
+        #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+        enum Letter {
+            A,
+            B,
+        }
+
+        impl Letter {
+            const ALL: [Letter; 2] = [Letter::B, Letter::A];
+
+            fn position(self) -> &'static str {
+                match self {
+                    Letter::A => "first",
+                    Letter::B => "second",
+                }
+            }
+        }
+
+        assert_eq!("second", Letter::B.position());
+        assert_eq!(1, Enumerated::position(&Letter::B));
+        assert_eq!([Letter::B, Letter::A], Letter::ALL);
+    }
+
+    #[test]
+    fn test_derive_cfg_gated_variants() {
+        use crate::enumerated::layout_hash;
+
+        #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+        enum Gated {
+            First,
+            #[cfg(any())]
+            Disabled,
+            #[cfg(test)]
+            Enabled,
+            Last,
+        }
+
+        assert_eq!(3, Gated::SIZE);
+        assert_eq!(&["First", "Enabled", "Last"], Gated::VARIANT_NAMES);
+        assert_eq!(
+            layout_hash(&["First", "Enabled", "Last"]),
+            Gated::LAYOUT_HASH
+        );
+        for (position, variant) in [Gated::First, Gated::Enabled, Gated::Last]
+            .into_iter()
+            .enumerate()
+        {
+            assert_eq!(position, variant.position());
+            assert_eq!(variant, Gated::from_position(position));
+        }
+        #[cfg(feature = "variants")]
+        assert_eq!(
+            &[Gated::First, Gated::Enabled, Gated::Last],
+            Gated::VARIANTS
+        );
+        crate::check_enumerated::<Gated>();
+
+        let enum_map = crate::EnumMap::<Gated, i32, { Gated::SIZE }>::new_default();
+        assert_eq!(0, enum_map[Gated::Last]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Position 3 is out of range of `Gated`")]
+    fn test_derive_cfg_gated_from_position_out_of_range() {
+        #[derive(Enumerated)]
+        enum Gated {
+            First,
+            #[cfg(any())]
+            Disabled,
+            Second,
+            Third,
+        }
+
+        Gated::from_position(3);
+    }
+
+    #[test]
+    fn test_derive_layout_hash() {
+        use crate::enumerated::{combine_layout_hash, layout_hash};
+
+        assert_eq!(layout_hash(&["North", "South"]), Direction::LAYOUT_HASH);
+
+        #[derive(Enumerated)]
+        enum Reordered {
+            South,
+            North,
+        }
+        assert_ne!(Direction::LAYOUT_HASH, Reordered::LAYOUT_HASH);
+
+        #[derive(Enumerated)]
+        enum Renamed {
+            North,
+            Southward,
+        }
+        assert_ne!(Direction::LAYOUT_HASH, Renamed::LAYOUT_HASH);
+
+        #[derive(Enumerated, Clone, Copy)]
+        struct Movement {
+            direction: Direction,
+            speed: Speed,
+        }
+        assert_eq!(
+            combine_layout_hash(
+                combine_layout_hash(layout_hash(&["direction", "speed"]), Direction::LAYOUT_HASH),
+                Speed::LAYOUT_HASH
+            ),
+            Movement::LAYOUT_HASH
+        );
+
+        #[derive(Enumerated, Clone, Copy)]
+        struct Swapped {
+            direction: Speed,
+            speed: Direction,
+        }
+        assert_ne!(Movement::LAYOUT_HASH, Swapped::LAYOUT_HASH);
+    }
+
+    #[cfg(feature = "variants")]
+    #[test]
+    fn test_derive_struct_variants() {
+        #[derive(Enumerated, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Movement {
+            direction: Direction,
+            speed: Speed,
+        }
+
+        assert_eq!(Movement::SIZE, Movement::VARIANTS.len());
+        // Cartesian product, the first field being the most significant one
+        assert_eq!(
+            Movement {
+                direction: Direction::North,
+                speed: Speed::Fast,
+            },
+            Movement::VARIANTS[2]
+        );
+        assert_eq!(
+            Movement {
+                direction: Direction::South,
+                speed: Speed::Slow,
+            },
+            Movement::VARIANTS[3]
+        );
+        for (position, variant) in Movement::VARIANTS.iter().enumerate() {
+            assert_eq!(position, variant.position());
+        }
+        // Same order as the derived `Ord`
+        assert!(Movement::VARIANTS.is_sorted());
+
+        #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+        struct Unit;
+        assert_eq!(&[Unit], Unit::VARIANTS);
     }
 }
@@ -0,0 +1,378 @@
+use crate::{EnumMap, Enumerated};
+use std::{
+    array,
+    marker::PhantomData,
+    sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/// A map of enum variants to independently lockable values. Each variant owns its own [RwLock],
+/// so threads working on different variants never block each other.
+///
+/// Lock poisoning is ignored. A panic while holding a slot's lock leaves the value in the state
+/// the panicking thread left it in, other slots are never affected.
+///
+/// # Examples
+///
+/// ```
+/// use enum_collections::{EnumLockMap, EnumMap, Enumerated};
+/// use std::thread;
+///
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+/// thread::scope(|scope| {
+///     scope.spawn(|| *map.write(&Letter::A) += 42);
+///     scope.spawn(|| *map.write(&Letter::B) += 24);
+/// });
+///
+/// assert_eq!(42, *map.read(&Letter::A));
+/// assert_eq!(24, *map.read(&Letter::B));
+/// ```
+pub struct EnumLockMap<K: Enumerated, V, const N: usize> {
+    data: [RwLock<V>; N],
+    _key: PhantomData<K>,
+}
+
+impl<K: Enumerated, V: Default, const N: usize> EnumLockMap<K, V, N> {
+    /// Creates a new EnumLockMap with type's default values for each variant.
+    ///
+    /// ```
+    /// use enum_collections::{EnumLockMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// assert_eq!(0, *map.read(&Letter::A));
+    /// assert_eq!(0, *map.read(&Letter::B));
+    /// ```
+    pub fn new_default() -> Self {
+        Self {
+            data: array::from_fn(|_| RwLock::new(V::default())),
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<K: Enumerated, V, const N: usize> EnumLockMap<K, V, N> {
+    /// Creates a new EnumLockMap, taking over the values of the given EnumMap.
+    ///
+    /// ```
+    /// use enum_collections::{EnumLockMap, EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::new(|| 42);
+    /// let map = EnumLockMap::from_enum_map(enum_map);
+    /// assert_eq!(42, *map.read(&Letter::A));
+    /// ```
+    pub fn from_enum_map(enum_map: EnumMap<K, V, N>) -> Self {
+        Self {
+            data: enum_map.data.map(RwLock::new),
+            _key: PhantomData,
+        }
+    }
+
+    /// Consumes the EnumLockMap, returning an EnumMap with the values of all the slots.
+    ///
+    /// ```
+    /// use enum_collections::{EnumLockMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// *map.write(&Letter::A) = 42;
+    /// let enum_map = map.into_inner();
+    /// assert_eq!(42, enum_map[Letter::A]);
+    /// assert_eq!(0, enum_map[Letter::B]);
+    /// ```
+    pub fn into_inner(self) -> EnumMap<K, V, N> {
+        EnumMap {
+            data: self
+                .data
+                .map(|slot| slot.into_inner().unwrap_or_else(PoisonError::into_inner)),
+            _key: PhantomData,
+        }
+    }
+
+    /// Locks the variant's slot with shared read access, blocking the current thread until it can be acquired.
+    /// Other variants are not locked.
+    ///
+    /// ```
+    /// use enum_collections::{EnumLockMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// let a = map.read(&Letter::A);
+    /// let b = map.read(&Letter::B);
+    /// assert_eq!(*a, *b);
+    /// ```
+    pub fn read(&self, key: &K) -> RwLockReadGuard<'_, V> {
+        self.data[key.position()]
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the variant's slot with exclusive write access, blocking the current thread until it can be acquired.
+    /// Other variants are not locked.
+    ///
+    /// ```
+    /// use enum_collections::{EnumLockMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// let mut a = map.write(&Letter::A);
+    /// *a = 42;
+    /// // Slot `B` is independent of the locked slot `A`
+    /// assert_eq!(0, *map.read(&Letter::B));
+    /// drop(a);
+    /// assert_eq!(42, *map.read(&Letter::A));
+    /// ```
+    pub fn write(&self, key: &K) -> RwLockWriteGuard<'_, V> {
+        self.data[key.position()]
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks multiple variants' slots with exclusive write access at once. The guards are returned
+    /// in the same order as the keys.
+    ///
+    /// Slots are always acquired in ascending order of their positions, regardless of the order of the keys,
+    /// so concurrent calls to `write_many` can not deadlock each other.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the same variant is present in the keys more than once.
+    ///
+    /// ```
+    /// use enum_collections::{EnumLockMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    ///    C,
+    /// }
+    ///
+    /// let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// let [mut c, mut a] = map.write_many([Letter::C, Letter::A]);
+    /// *a = 1;
+    /// *c = 3;
+    /// drop((a, c));
+    /// assert_eq!(1, *map.read(&Letter::A));
+    /// assert_eq!(0, *map.read(&Letter::B));
+    /// assert_eq!(3, *map.read(&Letter::C));
+    /// ```
+    pub fn write_many<const M: usize>(&self, keys: [K; M]) -> [RwLockWriteGuard<'_, V>; M] {
+        let positions = keys.map(|key| key.position());
+        let mut lock_order: [usize; M] = array::from_fn(|index| index);
+        lock_order.sort_unstable_by_key(|index| positions[*index]);
+        for pair in lock_order.windows(2) {
+            assert!(
+                positions[pair[0]] != positions[pair[1]],
+                "Duplicate key in EnumLockMap::write_many"
+            );
+        }
+
+        let mut guards: [Option<RwLockWriteGuard<'_, V>>; M] = array::from_fn(|_| None);
+        for index in lock_order {
+            guards[index] = Some(
+                self.data[positions[index]]
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner),
+            );
+        }
+        guards.map(|guard| guard.expect("Every key's slot is locked"))
+    }
+
+    /// Returns a mutable reference to the variant's value. No locking is required,
+    /// as the mutable borrow guarantees exclusive access.
+    ///
+    /// ```
+    /// use enum_collections::{EnumLockMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// *map.get_mut(&Letter::A) = 42;
+    /// assert_eq!(42, *map.read(&Letter::A));
+    /// ```
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        self.data[key.position()]
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<K: Enumerated, V: Clone, const N: usize> EnumLockMap<K, V, N> {
+    /// Creates a consistent copy of all the values as a plain EnumMap.
+    ///
+    /// All the slots are read-locked in ascending order of their positions first and only then the values
+    /// are cloned, so no write can be observed halfway through the snapshot.
+    ///
+    /// ```
+    /// use enum_collections::{EnumLockMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// *map.write(&Letter::B) = 24;
+    /// let snapshot = map.snapshot();
+    /// assert_eq!(0, snapshot[Letter::A]);
+    /// assert_eq!(24, snapshot[Letter::B]);
+    /// ```
+    pub fn snapshot(&self) -> EnumMap<K, V, N> {
+        let guards: [RwLockReadGuard<'_, V>; N] = array::from_fn(|index| {
+            self.data[index]
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+        });
+        EnumMap {
+            data: array::from_fn(|index| guards[index].clone()),
+            _key: PhantomData,
+        }
+    }
+}
+
+/// Converts an EnumMap into an EnumLockMap.
+///
+/// ```
+/// use enum_collections::{EnumLockMap, EnumMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let map: EnumLockMap<Letter, i32, { Letter::SIZE }> = EnumMap::new(|| 42).into();
+/// assert_eq!(42, *map.read(&Letter::B));
+/// ```
+impl<K: Enumerated, V, const N: usize> From<EnumMap<K, V, N>> for EnumLockMap<K, V, N> {
+    fn from(enum_map: EnumMap<K, V, N>) -> Self {
+        Self::from_enum_map(enum_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EnumLockMap, Enumerated};
+    use std::{sync::Barrier, thread};
+
+    #[derive(Enumerated)]
+    enum Letter {
+        A,
+        B,
+        C,
+    }
+
+    #[test]
+    fn independent_slots() {
+        let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+        let _a = map.write(&Letter::A);
+        // Must not block on the write lock held for `A`
+        *map.write(&Letter::B) = 42;
+        assert_eq!(42, *map.read(&Letter::B));
+
+        // Keys are borrowed, not moved
+        let key = Letter::C;
+        *map.write(&key) = 1;
+        assert_eq!(1, *map.read(&key));
+    }
+
+    #[test]
+    fn write_many_no_deadlock() {
+        let map = EnumLockMap::<Letter, u32, { Letter::SIZE }>::new_default();
+        let barrier = Barrier::new(2);
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                barrier.wait();
+                for _ in 0..1000 {
+                    let [mut a, mut c] = map.write_many([Letter::A, Letter::C]);
+                    *a += 1;
+                    *c += 1;
+                }
+            });
+            scope.spawn(|| {
+                barrier.wait();
+                for _ in 0..1000 {
+                    let [mut c, mut a] = map.write_many([Letter::C, Letter::A]);
+                    *a += 1;
+                    *c += 1;
+                }
+            });
+        });
+        let snapshot = map.snapshot();
+        assert_eq!(2000, snapshot[Letter::A]);
+        assert_eq!(0, snapshot[Letter::B]);
+        assert_eq!(2000, snapshot[Letter::C]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate key")]
+    fn write_many_duplicate_key() {
+        let map = EnumLockMap::<Letter, u32, { Letter::SIZE }>::new_default();
+        let _guards = map.write_many([Letter::B, Letter::A, Letter::B]);
+    }
+
+    #[test]
+    fn snapshot_consistent() {
+        // Writers keep the sum of `A` and `C` constant, every snapshot must observe the invariant.
+        let map = EnumLockMap::<Letter, i64, { Letter::SIZE }>::new_default();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    let [mut a, mut c] = map.write_many([Letter::A, Letter::C]);
+                    *a += 1;
+                    *c -= 1;
+                }
+            });
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    let snapshot = map.snapshot();
+                    assert_eq!(0, snapshot[Letter::A] + snapshot[Letter::C]);
+                }
+            });
+        });
+    }
+
+    #[test]
+    fn poisoned_slot() {
+        let map = EnumLockMap::<Letter, i32, { Letter::SIZE }>::new_default();
+        let _ = thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let mut a = map.write(&Letter::A);
+                    *a = 42;
+                    panic!("Poisoning the slot");
+                })
+                .join()
+        });
+        assert_eq!(42, *map.read(&Letter::A));
+        assert_eq!(42, map.into_inner()[Letter::A]);
+    }
+}
@@ -0,0 +1,222 @@
+//! Stepping through variants in the order of their positions, and slicing EnumMaps by ranges of variants.
+
+use std::ops::{Bound, Range, RangeBounds};
+
+use crate::{EnumMap, Enumerated, Keys};
+
+/// Navigation through the variants of an [Enumerated] type, in the order of their positions.
+/// For derived enums, this is the order of declaration.
+///
+/// Implemented for all `Copy` types implementing [Enumerated].
+///
+/// ```
+/// use enum_collections::{Enumerated, Navigable};
+/// #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+/// pub enum Tab {
+///    General,
+///    Network,
+///    Advanced,
+/// }
+///
+/// assert_eq!(Some(Tab::General), Tab::first());
+/// assert_eq!(Some(Tab::Advanced), Tab::last());
+/// assert_eq!(Some(Tab::Network), Tab::General.next());
+/// assert_eq!(None, Tab::Advanced.next());
+/// assert_eq!(Tab::General, Tab::Advanced.next_cyclic());
+/// ```
+pub trait Navigable: Enumerated + Copy {
+    /// The variant at the first position, `None` if there are no variants.
+    fn first() -> Option<Self> {
+        (Self::SIZE > 0).then(|| Self::from_position(0))
+    }
+
+    /// The variant at the last position, `None` if there are no variants.
+    fn last() -> Option<Self> {
+        Self::SIZE.checked_sub(1).map(Self::from_position)
+    }
+
+    /// The variant at the following position, `None` if this is the last variant.
+    fn next(self) -> Option<Self> {
+        let position = self.position() + 1;
+        (position < Self::SIZE).then(|| Self::from_position(position))
+    }
+
+    /// The variant at the preceding position, `None` if this is the first variant.
+    fn prev(self) -> Option<Self> {
+        self.position().checked_sub(1).map(Self::from_position)
+    }
+
+    /// The variant at the following position, wrapping around to the first variant after the last one.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, Navigable};
+    /// #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    /// pub enum Stage {
+    ///    Fetch,
+    ///    Decode,
+    ///    Execute,
+    /// }
+    ///
+    /// assert_eq!(Stage::Decode, Stage::Fetch.next_cyclic());
+    /// assert_eq!(Stage::Fetch, Stage::Execute.next_cyclic());
+    /// ```
+    fn next_cyclic(self) -> Self {
+        Self::from_position((self.position() + 1) % Self::SIZE)
+    }
+
+    /// The variant at the preceding position, wrapping around to the last variant before the first one.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, Navigable};
+    /// #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    /// pub enum Stage {
+    ///    Fetch,
+    ///    Decode,
+    ///    Execute,
+    /// }
+    ///
+    /// assert_eq!(Stage::Fetch, Stage::Decode.prev_cyclic());
+    /// assert_eq!(Stage::Execute, Stage::Fetch.prev_cyclic());
+    /// ```
+    fn prev_cyclic(self) -> Self {
+        Self::from_position((self.position() + Self::SIZE - 1) % Self::SIZE)
+    }
+
+    /// Iterates over a range of variants in the order of their positions.
+    /// A range whose start is positioned after its end is empty.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, Navigable};
+    /// #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    /// pub enum Stage {
+    ///    Fetch,
+    ///    Decode,
+    ///    Execute,
+    ///    Retire,
+    /// }
+    ///
+    /// let stages: Vec<Stage> = Stage::range(Stage::Decode..=Stage::Retire).collect();
+    /// assert_eq!(vec![Stage::Decode, Stage::Execute, Stage::Retire], stages);
+    ///
+    /// let stages: Vec<Stage> = Stage::range(..Stage::Execute).rev().collect();
+    /// assert_eq!(vec![Stage::Decode, Stage::Fetch], stages);
+    /// ```
+    fn range(range: impl RangeBounds<Self>) -> Keys<Self> {
+        positions::<Self>(range).map(Self::from_position)
+    }
+}
+
+impl<K: Enumerated + Copy> Navigable for K {}
+
+/// Converts a range of variants into a range of positions, clamped to `0..K::SIZE`.
+fn positions<K: Enumerated + Copy>(range: impl RangeBounds<K>) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(key) => key.position(),
+        Bound::Excluded(key) => key.position() + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(key) => key.position() + 1,
+        Bound::Excluded(key) => key.position(),
+        Bound::Unbounded => K::SIZE,
+    };
+    let end = end.min(K::SIZE);
+    start.min(end)..end
+}
+
+impl<K: Enumerated + Copy, V, const N: usize> EnumMap<K, V, N> {
+    /// Values of a range of variants, in the order of their positions.
+    /// A range whose start is positioned after its end yields an empty slice.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated, Clone, Copy)]
+    /// pub enum Stage {
+    ///    Fetch,
+    ///    Decode,
+    ///    Execute,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Stage, i32, { Stage::SIZE }>::new_inspect(|stage| *stage as i32);
+    /// assert_eq!(&[1, 2], enum_map.slice(Stage::Decode..=Stage::Execute));
+    /// assert_eq!(&[0], enum_map.slice(..Stage::Decode));
+    /// assert!(enum_map.slice(Stage::Execute..Stage::Fetch).is_empty());
+    /// ```
+    pub fn slice(&self, range: impl RangeBounds<K>) -> &[V] {
+        &self.data[positions::<K>(range)]
+    }
+
+    /// Mutable values of a range of variants, in the order of their positions.
+    /// A range whose start is positioned after its end yields an empty slice.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated, Clone, Copy)]
+    /// pub enum Stage {
+    ///    Fetch,
+    ///    Decode,
+    ///    Execute,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Stage, i32, { Stage::SIZE }>::new_default();
+    /// enum_map.slice_mut(Stage::Decode..).fill(42);
+    /// assert_eq!(0, enum_map[Stage::Fetch]);
+    /// assert_eq!(42, enum_map[Stage::Decode]);
+    /// assert_eq!(42, enum_map[Stage::Execute]);
+    /// ```
+    pub fn slice_mut(&mut self, range: impl RangeBounds<K>) -> &mut [V] {
+        &mut self.data[positions::<K>(range)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Enumerated, Navigable};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Tab {
+        General,
+        Network,
+        Advanced,
+    }
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Empty {}
+
+    #[test]
+    fn step() {
+        assert_eq!(Some(Tab::Network), Tab::General.next());
+        assert_eq!(Some(Tab::Advanced), Tab::Network.next());
+        assert_eq!(None, Tab::Advanced.next());
+        assert_eq!(None, Tab::General.prev());
+        assert_eq!(Some(Tab::General), Tab::Network.prev());
+        assert_eq!(Tab::Advanced, Tab::General.prev_cyclic());
+        assert_eq!(Tab::General, Tab::Advanced.next_cyclic());
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(None, Empty::first());
+        assert_eq!(None, Empty::last());
+        assert_eq!(0, Empty::range(..).count());
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(3, Tab::range(..).count());
+        assert_eq!(
+            vec![Tab::Network],
+            Tab::range(Tab::Network..Tab::Advanced).collect::<Vec<_>>()
+        );
+        assert_eq!(0, Tab::range(Tab::Advanced..=Tab::General).count());
+        assert_eq!(0, Tab::range(Tab::Network..Tab::Network).count());
+        assert_eq!(
+            vec![Tab::Advanced],
+            Tab::range((
+                std::ops::Bound::Excluded(Tab::Network),
+                std::ops::Bound::Unbounded
+            ))
+            .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,652 @@
+//! Map of enum variants to optional values, tracking presence in a flag per variant.
+
+use std::ops::{Index, IndexMut};
+
+use crate::{EnumMap, Enumerated};
+
+/// A map of enum variants to optional values, each variant holding a plain `V` slot and a presence flag.
+///
+/// `EnumMap<K, Option<V>, N>` spends the size of `Option<V>` on each variant. For values without a niche,
+/// such as `u64` or `f32`, the discriminant is padded to the alignment of `V`, e.g. 16 bytes per `u64`.
+/// EnumOptionMap stores `[V; N]` and a `bool` per variant inline instead, e.g. 9 bytes per `u64`.
+/// Values with a niche, such as `Box` or `String`, already fit `None` into `Option<V>`, so the extra
+/// `bool` per variant makes EnumOptionMap the larger of the two for them, e.g. 80 instead of 72 bytes
+/// for three `String` values.
+/// Absent variants hold `V::default()`, which is never observable through the map.
+///
+/// ```
+/// use enum_collections::{EnumMap, EnumOptionMap, Enumerated};
+///
+/// #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+/// pub enum Sensor {
+///     Pressure,
+///     Temperature,
+///     Humidity,
+/// }
+///
+/// let mut readings = EnumOptionMap::<Sensor, f32, { Sensor::SIZE }>::new();
+/// readings.insert(Sensor::Temperature, 21.5);
+/// assert_eq!(Some(&21.5), readings.get(&Sensor::Temperature));
+/// assert_eq!(None, readings.get(&Sensor::Humidity));
+/// assert_eq!(
+///     vec![(Sensor::Temperature, &21.5)],
+///     readings.iter_kv().collect::<Vec<_>>()
+/// );
+///
+/// // Conversions to and from maps of `Option<V>`
+/// let options: EnumMap<Sensor, Option<f32>, { Sensor::SIZE }> = readings.clone().into();
+/// assert_eq!(None, options[Sensor::Pressure]);
+/// assert_eq!(readings, EnumOptionMap::from(options));
+/// ```
+pub struct EnumOptionMap<K: Enumerated, V, const N: usize> {
+    values: EnumMap<K, V, N>,
+    present: [bool; N],
+}
+
+impl<K: Enumerated, V: Default, const N: usize> EnumOptionMap<K, V, N> {
+    /// Creates an EnumOptionMap with no values present.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// assert!(option_map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            values: EnumMap::new_default(),
+            present: [false; N],
+        }
+    }
+
+    /// Removes the value of the given key. Returns the removed value, if present.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::A, 1);
+    /// assert_eq!(Some(1), option_map.remove(&Letter::A));
+    /// assert_eq!(None, option_map.remove(&Letter::A));
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if std::mem::replace(&mut self.present[key.position()], false) {
+            Some(std::mem::take(&mut self.values[key]))
+        } else {
+            None
+        }
+    }
+
+    /// Removes all values.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::B, 1);
+    /// option_map.clear();
+    /// assert!(option_map.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.values.clear_set_default();
+        self.present = [false; N];
+    }
+}
+
+impl<K: Enumerated, V, const N: usize> EnumOptionMap<K, V, N> {
+    /// Value of the given key, `None` if not present.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::B, 42);
+    /// assert_eq!(None, option_map.get(&Letter::A));
+    /// assert_eq!(Some(&42), option_map.get(&Letter::B));
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        if self.present[key.position()] {
+            Some(&self.values[key])
+        } else {
+            None
+        }
+    }
+
+    /// Mutable reference to the value of the given key, `None` if not present.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::B, 42);
+    /// if let Some(value) = option_map.get_mut(&Letter::B) {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(Some(&43), option_map.get(&Letter::B));
+    /// ```
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if self.present[key.position()] {
+            Some(&mut self.values[key])
+        } else {
+            None
+        }
+    }
+
+    /// Inserts a value for the given key. Returns the previous value, if present.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// assert_eq!(None, option_map.insert(Letter::A, 1));
+    /// assert_eq!(Some(1), option_map.insert(Letter::A, 2));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let previous = std::mem::replace(&mut self.values[&key], value);
+        if !std::mem::replace(&mut self.present[key.position()], true) {
+            None
+        } else {
+            Some(previous)
+        }
+    }
+
+    /// Returns `true` if a value of the given key is present.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::A, 1);
+    /// assert!(option_map.contains_key(&Letter::A));
+    /// assert!(!option_map.contains_key(&Letter::B));
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.present[key.position()]
+    }
+
+    /// Number of values present.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::B, 1);
+    /// assert_eq!(1, option_map.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.present.iter().filter(|present| **present).count()
+    }
+
+    /// Returns `true` if no value is present.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// assert!(option_map.is_empty());
+    /// option_map.insert(Letter::B, 1);
+    /// assert!(!option_map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        !self.present.contains(&true)
+    }
+
+    /// Iterates over the present keys and their values, in the order of the keys' positions.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated, Debug, PartialEq)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    ///    C,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::C, 3);
+    /// option_map.insert(Letter::A, 1);
+    /// assert_eq!(
+    ///     vec![(Letter::A, &1), (Letter::C, &3)],
+    ///     option_map.iter_kv().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn iter_kv(&self) -> impl Iterator<Item = (K, &V)> {
+        self.positions()
+            .map(|position| (K::from_position(position), &self.values.data[position]))
+    }
+
+    /// Mutably iterates over the present keys and their values, in the order of the keys' positions.
+    /// Only the values are mutable.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::B, 1);
+    /// for (_letter, value) in option_map.iter_kv_mut() {
+    ///     *value *= 10;
+    /// }
+    /// assert_eq!(Some(&10), option_map.get(&Letter::B));
+    /// ```
+    pub fn iter_kv_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> {
+        let present = &self.present;
+        self.values
+            .data
+            .iter_mut()
+            .enumerate()
+            .filter(|(position, _)| present[*position])
+            .map(|(position, value)| (K::from_position(position), value))
+    }
+
+    /// Iterates over the present values, in the order of their keys' positions.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+    /// option_map.insert(Letter::B, 2);
+    /// assert_eq!(vec![&2], option_map.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &V> {
+        self.positions().map(|position| &self.values.data[position])
+    }
+
+    /// Positions of the present values, in ascending order.
+    fn positions(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..N).filter(|position| self.present[*position])
+    }
+}
+
+impl<K: Enumerated, V: Default, const N: usize> Default for EnumOptionMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Clones the EnumOptionMap, including the default values of absent keys.
+impl<K: Enumerated, V: Clone, const N: usize> Clone for EnumOptionMap<K, V, N> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            present: self.present,
+        }
+    }
+}
+
+/// Indexes present values.
+///
+/// # Panics
+///
+/// Panics if no value of the key is present. Use [EnumOptionMap::get] to look up keys that may be absent.
+///
+/// ```
+/// use enum_collections::{EnumOptionMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+/// option_map.insert(Letter::B, 42);
+/// assert_eq!(42, option_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V, const N: usize> Index<K> for EnumOptionMap<K, V, N> {
+    type Output = V;
+
+    fn index(&self, key: K) -> &Self::Output {
+        &self[&key]
+    }
+}
+
+/// Indexes present values by borrowed keys.
+///
+/// # Panics
+///
+/// Panics if no value of the key is present.
+impl<K: Enumerated, V, const N: usize> Index<&K> for EnumOptionMap<K, V, N> {
+    type Output = V;
+
+    fn index(&self, key: &K) -> &Self::Output {
+        let position = key.position();
+        self.get(key).unwrap_or_else(|| {
+            panic!(
+                "No value present at position {} of `{}`",
+                position,
+                std::any::type_name::<K>()
+            )
+        })
+    }
+}
+
+/// Mutably indexes present values.
+///
+/// # Panics
+///
+/// Panics if no value of the key is present. Use [EnumOptionMap::insert] to add values.
+///
+/// ```
+/// use enum_collections::{EnumOptionMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+/// option_map.insert(Letter::B, 42);
+/// option_map[Letter::B] += 1;
+/// assert_eq!(43, option_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V, const N: usize> IndexMut<K> for EnumOptionMap<K, V, N> {
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        &mut self[&key]
+    }
+}
+
+/// Mutably indexes present values by borrowed keys.
+///
+/// # Panics
+///
+/// Panics if no value of the key is present.
+impl<K: Enumerated, V, const N: usize> IndexMut<&K> for EnumOptionMap<K, V, N> {
+    fn index_mut(&mut self, key: &K) -> &mut Self::Output {
+        let position = key.position();
+        self.get_mut(key).unwrap_or_else(|| {
+            panic!(
+                "No value present at position {} of `{}`",
+                position,
+                std::any::type_name::<K>()
+            )
+        })
+    }
+}
+
+/// Collects key-value pairs, the last value of a key repeated wins.
+///
+/// ```
+/// use enum_collections::{EnumOptionMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let option_map: EnumOptionMap<Letter, u64, { Letter::SIZE }> =
+///     [(Letter::B, 1), (Letter::B, 2)].into_iter().collect();
+/// assert_eq!(1, option_map.len());
+/// assert_eq!(2, option_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V: Default, const N: usize> FromIterator<(K, V)> for EnumOptionMap<K, V, N> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut option_map = Self::new();
+        for (key, value) in iter {
+            option_map.insert(key, value);
+        }
+        option_map
+    }
+}
+
+/// Keeps the `Some` values, `None` becoming absent.
+///
+/// ```
+/// use enum_collections::{EnumMap, EnumOptionMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut enum_map = EnumMap::<Letter, Option<u64>, { Letter::SIZE }>::new_option();
+/// enum_map[Letter::B] = Some(42);
+/// let option_map = EnumOptionMap::from(enum_map);
+/// assert_eq!(None, option_map.get(&Letter::A));
+/// assert_eq!(Some(&42), option_map.get(&Letter::B));
+/// ```
+impl<K: Enumerated, V: Default, const N: usize> From<EnumMap<K, Option<V>, N>>
+    for EnumOptionMap<K, V, N>
+{
+    fn from(enum_map: EnumMap<K, Option<V>, N>) -> Self {
+        let present = std::array::from_fn(|position| enum_map.data[position].is_some());
+        let data = enum_map.data.map(Option::unwrap_or_default);
+        Self {
+            values: EnumMap::from_array(data),
+            present,
+        }
+    }
+}
+
+/// Map of `Option<V>`, `None` for keys not present.
+///
+/// ```
+/// use enum_collections::{EnumMap, EnumOptionMap, Enumerated};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+/// option_map.insert(Letter::B, 42);
+/// let enum_map: EnumMap<Letter, Option<u64>, { Letter::SIZE }> = option_map.into();
+/// assert_eq!(None, enum_map[Letter::A]);
+/// assert_eq!(Some(42), enum_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V, const N: usize> From<EnumOptionMap<K, V, N>> for EnumMap<K, Option<V>, N> {
+    fn from(option_map: EnumOptionMap<K, V, N>) -> Self {
+        let present = option_map.present;
+        let mut position = 0;
+        EnumMap::from_array(option_map.values.data.map(|value| {
+            let value = present[position].then_some(value);
+            position += 1;
+            value
+        }))
+    }
+}
+
+/// Prints the present keys and their values as a map.
+///
+/// ```
+/// use enum_collections::{EnumOptionMap, Enumerated};
+/// #[derive(Enumerated, Debug)]
+/// enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+/// option_map.insert(Letter::B, 42);
+/// assert_eq!("{B: 42}", format!("{:?}", option_map));
+/// ```
+#[cfg(feature = "debug")]
+impl<K: Enumerated + std::fmt::Debug, V: std::fmt::Debug, const N: usize> std::fmt::Debug
+    for EnumOptionMap<K, V, N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter_kv()).finish()
+    }
+}
+
+#[cfg(feature = "eq")]
+mod eq {
+    use super::{EnumOptionMap, Enumerated};
+
+    /// Two maps are equal if the same keys are present, with equal values. Slots of absent keys are not compared.
+    ///
+    /// ```
+    /// use enum_collections::{EnumOptionMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let first: EnumOptionMap<Letter, u64, { Letter::SIZE }> = [(Letter::A, 1)].into_iter().collect();
+    /// let second: EnumOptionMap<Letter, u64, { Letter::SIZE }> = [(Letter::A, 1)].into_iter().collect();
+    /// let third: EnumOptionMap<Letter, u64, { Letter::SIZE }> = [(Letter::B, 1)].into_iter().collect();
+    /// assert!(first == second);
+    /// assert!(first != third);
+    /// ```
+    impl<K: Enumerated, V: PartialEq, const N: usize> PartialEq for EnumOptionMap<K, V, N> {
+        fn eq(&self, other: &Self) -> bool {
+            self.present == other.present && self.iter().eq(other.iter())
+        }
+    }
+
+    impl<K: Enumerated, V: Eq, const N: usize> Eq for EnumOptionMap<K, V, N> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bounded, EnumMap, EnumOptionMap, Enumerated};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Letter {
+        A,
+        B,
+        C,
+    }
+
+    /// Sizes of the EnumOptionMap and of the EnumMap of options with the same keys and values
+    fn sizes<K: Enumerated, V, const N: usize>() -> (usize, usize) {
+        (
+            size_of::<EnumOptionMap<K, V, N>>(),
+            size_of::<EnumMap<K, Option<V>, N>>(),
+        )
+    }
+
+    /// Presence is stored inline, never larger than options
+    #[test]
+    fn size_against_options() {
+        #[derive(Enumerated)]
+        enum Single {
+            Only,
+        }
+        type Wide = Bounded<0, 99>;
+
+        assert_eq!((32, 48), sizes::<Letter, u64, { Letter::SIZE }>());
+        assert_eq!((16, 24), sizes::<Letter, f32, { Letter::SIZE }>());
+        assert_eq!((6, 6), sizes::<Letter, u8, { Letter::SIZE }>());
+        assert_eq!((16, 16), sizes::<Single, u64, { Single::SIZE }>());
+        assert_eq!((904, 1600), sizes::<Wide, u64, { Wide::SIZE }>());
+        assert_eq!((200, 200), sizes::<Wide, u8, { Wide::SIZE }>());
+        // A niche already holds `None`, the presence flags only add to it
+        assert_eq!((80, 72), sizes::<Letter, String, { Letter::SIZE }>());
+
+        let (option_map, options) = sizes::<Wide, u128, { Wide::SIZE }>();
+        assert!(option_map < options);
+        let (option_map, options) = sizes::<Letter, [u16; 3], { Letter::SIZE }>();
+        assert!(option_map <= options);
+    }
+
+    #[test]
+    fn removed_values_are_absent() {
+        let mut option_map = EnumOptionMap::<Letter, String, { Letter::SIZE }>::new();
+        option_map.insert(Letter::A, "a".to_owned());
+        option_map.insert(Letter::C, "c".to_owned());
+        assert_eq!(Some("a".to_owned()), option_map.remove(&Letter::A));
+        assert_eq!(None, option_map.get(&Letter::A));
+        assert_eq!(1, option_map.len());
+        assert_eq!(
+            vec![Letter::C],
+            option_map
+                .iter_kv()
+                .map(|(letter, _)| letter)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// Absent slots are not compared, even though they hold values
+    #[cfg(feature = "eq")]
+    #[test]
+    fn eq_ignores_absent() {
+        let mut option_map = EnumOptionMap::<Letter, String, { Letter::SIZE }>::new();
+        option_map.insert(Letter::A, "a".to_owned());
+        option_map.insert(Letter::C, "c".to_owned());
+        option_map.remove(&Letter::A);
+
+        let mut other = EnumOptionMap::<Letter, String, { Letter::SIZE }>::new();
+        other.insert(Letter::C, "c".to_owned());
+        assert!(other == option_map);
+        other.insert(Letter::B, "b".to_owned());
+        assert!(other != option_map);
+    }
+
+    #[test]
+    fn options_round_trip() {
+        let mut enum_map = EnumMap::<Letter, Option<u64>, { Letter::SIZE }>::new_option();
+        enum_map[Letter::B] = Some(0);
+        enum_map[Letter::C] = Some(3);
+
+        let option_map = EnumOptionMap::from(enum_map);
+        assert_eq!(2, option_map.len());
+        assert_eq!(Some(&0), option_map.get(&Letter::B)); // A present default value
+        assert_eq!(None, option_map.get(&Letter::A));
+
+        let options: EnumMap<Letter, Option<u64>, { Letter::SIZE }> = option_map.into();
+        // Compared by values, `PartialEq` requires the `eq` feature
+        assert!(enum_map.iter().eq(options.iter()));
+    }
+
+    #[test]
+    #[should_panic(expected = "No value present at position 1")]
+    fn index_absent() {
+        let option_map = EnumOptionMap::<Letter, u64, { Letter::SIZE }>::new();
+        let _ = option_map[Letter::B];
+    }
+}
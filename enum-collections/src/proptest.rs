@@ -0,0 +1,108 @@
+//! Property testing support using the `proptest` crate.
+//!
+//! Each value of an [EnumMap] is generated by its own strategy, so failing cases shrink entry by entry.
+
+use proptest::{
+    arbitrary::{Arbitrary, any_with},
+    array::UniformArrayStrategy,
+    strategy::{Map, Strategy},
+};
+use std::{fmt::Debug, marker::PhantomData, ops::Range};
+
+use crate::{EnumMap, Enumerated};
+
+impl<K: Enumerated + Debug, V: Arbitrary, const N: usize> Arbitrary for EnumMap<K, V, N> {
+    type Parameters = V::Parameters;
+    type Strategy = Map<UniformArrayStrategy<V::Strategy, [V; N]>, fn([V; N]) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        UniformArrayStrategy::new(any_with::<V>(args)).prop_map(|data| Self {
+            data,
+            _key: PhantomData,
+        })
+    }
+}
+
+/// Strategy generating variants of an [Enumerated] type, see [any_variant].
+pub type VariantStrategy<K> = Map<Range<usize>, fn(usize) -> K>;
+
+/// Strategy generating any variant of an [Enumerated] type.
+/// Variants shrink towards the first position.
+///
+/// ```
+/// use enum_collections::{Enumerated, any_variant};
+/// use proptest::prelude::*;
+///
+/// #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// proptest! {
+///     fn round_trip(letter in any_variant::<Letter>()) {
+///         prop_assert_eq!(letter, Letter::from_position(letter.position()));
+///     }
+/// }
+/// round_trip();
+/// ```
+pub fn any_variant<K: Enumerated + Debug>() -> VariantStrategy<K> {
+    (0..K::SIZE).prop_map(K::from_position)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+
+    use crate::{EnumMap, Enumerated, any_variant};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Setting {
+        Volume,
+        Brightness,
+        Contrast,
+    }
+
+    proptest! {
+        #[test]
+        fn variants_in_range(setting in any_variant::<Setting>()) {
+            prop_assert!(setting.position() < Setting::SIZE);
+        }
+
+        #[test]
+        fn enum_map(enum_map in any::<EnumMap<Setting, u8, { Setting::SIZE }>>()) {
+            prop_assert_eq!(Setting::SIZE, enum_map.iter().count());
+        }
+    }
+
+    #[test]
+    fn shrinks_per_entry() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = any::<EnumMap<Setting, u8, { Setting::SIZE }>>()
+            .new_tree(&mut runner)
+            .unwrap();
+        // Only the volume is considered failing, the other entries shrink to their minimum
+        let failing =
+            |enum_map: &EnumMap<Setting, u8, { Setting::SIZE }>| enum_map[Setting::Volume] >= 10;
+        while !failing(&tree.current()) {
+            tree = any::<EnumMap<Setting, u8, { Setting::SIZE }>>()
+                .new_tree(&mut runner)
+                .unwrap();
+        }
+        let mut minimal = tree.current();
+        loop {
+            let current = tree.current();
+            if failing(&current) {
+                minimal = current;
+                if !tree.simplify() {
+                    break;
+                }
+            } else if !tree.complicate() {
+                break;
+            }
+        }
+        assert_eq!(10, minimal[Setting::Volume]);
+        assert_eq!(0, minimal[Setting::Brightness]);
+        assert_eq!(0, minimal[Setting::Contrast]);
+    }
+}
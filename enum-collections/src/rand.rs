@@ -0,0 +1,267 @@
+//! Random sampling of variants using the `rand` crate.
+
+use rand::{Rng, distr::Distribution};
+use std::{fmt::Display, marker::PhantomData};
+
+use crate::{EnumMap, Enumerated};
+
+/// Samples variants of an [Enumerated] type uniformly, each variant being equally likely.
+///
+/// ```
+/// use enum_collections::{Enumerated, UniformVariant};
+/// use rand::distr::Distribution;
+///
+/// #[derive(Enumerated, Debug, PartialEq)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let letter: Letter = UniformVariant.sample(&mut rand::rng());
+/// assert!(letter == Letter::A || letter == Letter::B);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniformVariant;
+
+impl<K: Enumerated> Distribution<K> for UniformVariant {
+    /// # Panics
+    ///
+    /// Panics if `K` has no variants.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> K {
+        K::from_position(rng.random_range(0..K::SIZE))
+    }
+}
+
+/// Samples variants of an [Enumerated] type with probabilities proportional to their weights.
+///
+/// Constructed from an EnumMap of weights in `O(N)`. Implements Vose's alias method, every sample
+/// takes `O(1)` regardless of the number of variants.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated, WeightedVariant, em};
+/// use rand::distr::Distribution;
+///
+/// #[derive(Enumerated, Debug, PartialEq)]
+/// pub enum RequestKind {
+///    Read,
+///    Write,
+///    Delete,
+/// }
+///
+/// let weights = em!(RequestKind, f64, Read => 8.0, Write => 2.0, Delete => 0.0);
+/// let distribution = WeightedVariant::new(&weights).unwrap();
+/// let kind = distribution.sample(&mut rand::rng());
+/// assert_ne!(RequestKind::Delete, kind);
+/// ```
+pub struct WeightedVariant<K: Enumerated, const N: usize> {
+    /// Probability of keeping the uniformly chosen position, instead of using its alias.
+    probabilities: [f64; N],
+    aliases: [usize; N],
+    _key: PhantomData<K>,
+}
+
+/// Reasons weights can't be used to construct a [WeightedVariant] distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightError {
+    /// A weight at the given position is negative.
+    Negative(usize),
+    /// A weight at the given position is either NaN or infinite.
+    NotFinite(usize),
+    /// All the weights are zero, or there are no variants at all.
+    AllZero,
+}
+
+impl Display for WeightError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeightError::Negative(position) => {
+                write!(f, "Weight at position {} is negative", position)
+            }
+            WeightError::NotFinite(position) => {
+                write!(f, "Weight at position {} is not a finite number", position)
+            }
+            WeightError::AllZero => write!(f, "All weights are zero"),
+        }
+    }
+}
+
+impl std::error::Error for WeightError {}
+
+impl<K: Enumerated, const N: usize> WeightedVariant<K, N> {
+    /// Creates the distribution from weights of each variant. Weights don't have to sum up to one.
+    /// Variants with zero weight are never sampled.
+    ///
+    /// Returns an error if any weight is negative, NaN or infinite, or if all the weights are zero.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated, WeightError, WeightedVariant, em};
+    ///
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// assert!(WeightedVariant::new(&em!(Letter, f64, A => 1.0, B => 3.0)).is_ok());
+    /// assert_eq!(
+    ///     Some(WeightError::Negative(1)),
+    ///     WeightedVariant::new(&em!(Letter, f64, A => 1.0, B => -3.0)).err()
+    /// );
+    /// assert_eq!(
+    ///     Some(WeightError::NotFinite(0)),
+    ///     WeightedVariant::new(&em!(Letter, f64, A => f64::NAN, B => 3.0)).err()
+    /// );
+    /// assert_eq!(
+    ///     Some(WeightError::AllZero),
+    ///     WeightedVariant::new(&em!(Letter, f64, A => 0.0, B => 0.0)).err()
+    /// );
+    /// ```
+    pub fn new(weights: &EnumMap<K, f64, N>) -> Result<Self, WeightError> {
+        for (position, weight) in weights.iter().enumerate() {
+            if !weight.is_finite() {
+                return Err(WeightError::NotFinite(position));
+            }
+            if *weight < 0.0 {
+                return Err(WeightError::Negative(position));
+            }
+        }
+        let max = weights.iter().copied().fold(0.0, f64::max);
+        if max <= 0.0 {
+            return Err(WeightError::AllZero);
+        }
+        // Relative to the largest weight, the sum of finite weights can't overflow
+        let relative: [f64; N] = std::array::from_fn(|index| weights.data[index] / max);
+        let total: f64 = relative.iter().sum();
+
+        // Weights scaled so that their average is one, divided before multiplied to stay finite
+        let mut scaled: [f64; N] = std::array::from_fn(|index| relative[index] / total * N as f64);
+        let mut probabilities = [1.0; N];
+        let mut aliases: [usize; N] = std::array::from_fn(|index| index);
+
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..N).partition(|index| scaled[*index] < 1.0);
+        while let (Some(less), Some(more)) = (small.pop(), large.last().copied()) {
+            probabilities[less] = scaled[less];
+            aliases[less] = more;
+            scaled[more] -= 1.0 - scaled[less];
+            if scaled[more] < 1.0 {
+                large.pop();
+                small.push(more);
+            }
+        }
+        // Leftovers are exactly one, up to floating point rounding errors, and keep the default probability of one.
+
+        Ok(Self {
+            probabilities,
+            aliases,
+            _key: PhantomData,
+        })
+    }
+}
+
+impl<K: Enumerated, const N: usize> Distribution<K> for WeightedVariant<K, N> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> K {
+        let position = rng.random_range(0..N);
+        if rng.random::<f64>() < self.probabilities[position] {
+            K::from_position(position)
+        } else {
+            K::from_position(self.aliases[position])
+        }
+    }
+}
+
+impl<K: Enumerated, const N: usize> Clone for WeightedVariant<K, N> {
+    fn clone(&self) -> Self {
+        Self {
+            probabilities: self.probabilities,
+            aliases: self.aliases,
+            _key: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, distr::Distribution, rngs::StdRng};
+
+    use crate::{EnumMap, Enumerated, UniformVariant, WeightError, WeightedVariant};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum RequestKind {
+        Read,
+        Write,
+        Update,
+        Delete,
+    }
+
+    fn frequencies(
+        distribution: &impl Distribution<RequestKind>,
+        samples: usize,
+    ) -> EnumMap<RequestKind, f64, { RequestKind::SIZE }> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts = EnumMap::<RequestKind, usize, { RequestKind::SIZE }>::new_default();
+        for _ in 0..samples {
+            counts[distribution.sample(&mut rng)] += 1;
+        }
+        EnumMap::new_inspect(|kind: &RequestKind| counts[*kind] as f64 / samples as f64)
+    }
+
+    #[test]
+    fn uniform() {
+        let frequencies = frequencies(&UniformVariant, 100_000);
+        for frequency in frequencies.iter() {
+            assert!((frequency - 0.25).abs() < 0.01, "{}", frequency);
+        }
+    }
+
+    #[test]
+    fn weighted() {
+        let mut weights = EnumMap::<RequestKind, f64, { RequestKind::SIZE }>::new_default();
+        weights[RequestKind::Read] = 6.0;
+        weights[RequestKind::Write] = 3.0;
+        weights[RequestKind::Update] = 1.0;
+        let distribution = WeightedVariant::new(&weights).unwrap();
+
+        let frequencies = frequencies(&distribution, 100_000);
+        assert!((frequencies[RequestKind::Read] - 0.6).abs() < 0.01);
+        assert!((frequencies[RequestKind::Write] - 0.3).abs() < 0.01);
+        assert!((frequencies[RequestKind::Update] - 0.1).abs() < 0.01);
+        assert_eq!(0.0, frequencies[RequestKind::Delete]);
+    }
+
+    #[test]
+    fn single_nonzero_weight() {
+        let mut weights = EnumMap::<RequestKind, f64, { RequestKind::SIZE }>::new_default();
+        weights[RequestKind::Delete] = 0.5;
+        let distribution = WeightedVariant::new(&weights).unwrap();
+        assert_eq!(1.0, frequencies(&distribution, 1000)[RequestKind::Delete]);
+    }
+
+    #[test]
+    fn invalid_weights() {
+        let mut weights = EnumMap::<RequestKind, f64, { RequestKind::SIZE }>::new_with_all(1.0);
+        weights[RequestKind::Update] = f64::INFINITY;
+        assert_eq!(
+            Some(WeightError::NotFinite(2)),
+            WeightedVariant::new(&weights).err()
+        );
+    }
+
+    /// Finite weights whose sum overflows are valid
+    #[test]
+    fn huge_weights() {
+        let mut weights = EnumMap::<RequestKind, f64, { RequestKind::SIZE }>::new_default();
+        weights[RequestKind::Read] = f64::MAX / 2.0;
+        weights[RequestKind::Write] = f64::MAX / 2.0;
+        let distribution = WeightedVariant::new(&weights).unwrap();
+        let halves = frequencies(&distribution, 100_000);
+        assert!((halves[RequestKind::Read] - 0.5).abs() < 0.01);
+        assert!((halves[RequestKind::Write] - 0.5).abs() < 0.01);
+
+        let weights = EnumMap::<RequestKind, f64, { RequestKind::SIZE }>::new_with_all(f64::MAX);
+        let frequencies = frequencies(&WeightedVariant::new(&weights).unwrap(), 100_000);
+        for frequency in frequencies.iter() {
+            assert!((frequency - 0.25).abs() < 0.01, "{}", frequency);
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Rayon's parallel iterators and parallel constructors for [EnumMap].
+//!
+//! All of them operate on the contiguous array backing the EnumMap, which makes
+//! the work trivially splittable.
+
+use rayon::prelude::*;
+use std::marker::PhantomData;
+
+use crate::{EnumMap, Enumerated};
+
+impl<K: Enumerated, V, const N: usize> EnumMap<K, V, N> {
+    /// Iterates over the EnumMap's values in parallel.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// use rayon::prelude::*;
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::new(|| 42);
+    /// assert_eq!(84, enum_map.par_iter().sum::<i32>());
+    /// ```
+    pub fn par_iter(&self) -> rayon::slice::Iter<'_, V>
+    where
+        V: Sync,
+    {
+        self.data.par_iter()
+    }
+
+    /// Mutably iterates over the EnumMap's values in parallel.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// use rayon::prelude::*;
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::new(|| 42);
+    /// enum_map.par_iter_mut().for_each(|value| *value += 10);
+    /// assert_eq!(52, enum_map[Letter::A]);
+    /// assert_eq!(52, enum_map[Letter::B]);
+    /// ```
+    pub fn par_iter_mut(&mut self) -> rayon::slice::IterMut<'_, V>
+    where
+        V: Send,
+    {
+        self.data.par_iter_mut()
+    }
+
+    /// Iterates over the EnumMap's key-value pairs in parallel.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// use rayon::prelude::*;
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::new(|| 42);
+    /// enum_map
+    ///     .par_iter_kv()
+    ///     .for_each(|(_letter, value)| assert_eq!(42, *value));
+    /// ```
+    pub fn par_iter_kv(&self) -> rayon::iter::Zip<ParKeys<K>, rayon::slice::Iter<'_, V>>
+    where
+        K: Send,
+        V: Sync,
+    {
+        par_keys::<K>(N).zip(self.data.par_iter())
+    }
+
+    /// Mutably iterates over the EnumMap's key-value pairs in parallel. Only the values are mutable.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// use rayon::prelude::*;
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::new_default();
+    /// enum_map
+    ///     .par_iter_kv_mut()
+    ///     .for_each(|(letter, value)| *value = letter.position() as i32);
+    /// assert_eq!(0, enum_map[Letter::A]);
+    /// assert_eq!(1, enum_map[Letter::B]);
+    /// ```
+    pub fn par_iter_kv_mut(&mut self) -> rayon::iter::Zip<ParKeys<K>, rayon::slice::IterMut<'_, V>>
+    where
+        K: Send,
+        V: Send,
+    {
+        par_keys::<K>(N).zip(self.data.par_iter_mut())
+    }
+
+    /// Creates a new EnumMap where value of each variant is produced by the provided function, in parallel.
+    /// The function receives the enum variant being initialized for inspection.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::par_new_inspect(|letter| {
+    ///    match letter {
+    ///       Letter::A => 42,
+    ///       Letter::B => 24,
+    ///    }
+    /// });
+    /// assert_eq!(42, enum_map[Letter::A]);
+    /// assert_eq!(24, enum_map[Letter::B]);
+    /// ```
+    pub fn par_new_inspect(init: impl Fn(&K) -> V + Sync + Send) -> Self
+    where
+        K: Send,
+        V: Send,
+    {
+        let values: Vec<V> = par_keys::<K>(N).map(|key| init(&key)).collect();
+        Self {
+            data: collect_array(values),
+            _key: PhantomData,
+        }
+    }
+
+    /// Consumes the EnumMap and creates a new one, where each value is mapped by the provided function, in parallel.
+    /// The function receives the enum variant of the value for inspection.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Letter, i32, { Letter::SIZE }>::new(|| 42);
+    /// let mapped = enum_map.par_map(|letter, value| match letter {
+    ///     Letter::A => value.to_string(),
+    ///     Letter::B => String::new(),
+    /// });
+    /// assert_eq!("42", mapped[Letter::A]);
+    /// assert_eq!("", mapped[Letter::B]);
+    /// ```
+    pub fn par_map<W: Send>(self, mapper: impl Fn(&K, V) -> W + Sync + Send) -> EnumMap<K, W, N>
+    where
+        K: Send,
+        V: Send,
+    {
+        let values: Vec<W> = par_keys::<K>(N)
+            .zip(self.data)
+            .map(|(key, value)| mapper(&key, value))
+            .collect();
+        EnumMap {
+            data: collect_array(values),
+            _key: PhantomData,
+        }
+    }
+}
+
+/// Parallel iterator over the variants of `K`, in the order of their positions.
+pub type ParKeys<K> = rayon::iter::Map<rayon::range::Iter<usize>, fn(usize) -> K>;
+
+/// Variants of `K` at positions `0..size`, in parallel.
+fn par_keys<K: Enumerated + Send>(size: usize) -> ParKeys<K> {
+    (0..size).into_par_iter().map(K::from_position)
+}
+
+/// Converts values collected by a parallel iterator back into the array backing an EnumMap.
+fn collect_array<V, const N: usize>(values: Vec<V>) -> [V; N] {
+    match values.try_into() {
+        Ok(data) => data,
+        Err(values) => panic!(
+            "EnumMap requires exactly {} values, {} produced",
+            N,
+            values.len()
+        ),
+    }
+}
+
+/// Consumes the EnumMap, iterating over its values in parallel.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated};
+/// use rayon::prelude::*;
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let enum_map = EnumMap::<Letter, String, { Letter::SIZE }>::new(|| "a".to_string());
+/// let joined: String = enum_map.into_par_iter().collect();
+/// assert_eq!("aa", joined);
+/// ```
+impl<K: Enumerated, V: Send, const N: usize> IntoParallelIterator for EnumMap<K, V, N> {
+    type Iter = rayon::array::IntoIter<V, N>;
+    type Item = V;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.data.into_par_iter()
+    }
+}
+
+impl<'a, K: Enumerated, V: Sync, const N: usize> IntoParallelIterator for &'a EnumMap<K, V, N> {
+    type Iter = rayon::slice::Iter<'a, V>;
+    type Item = &'a V;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.data.par_iter()
+    }
+}
+
+impl<'a, K: Enumerated, V: Send, const N: usize> IntoParallelIterator for &'a mut EnumMap<K, V, N> {
+    type Iter = rayon::slice::IterMut<'a, V>;
+    type Item = &'a mut V;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.data.par_iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use crate::{EnumMap, Enumerated};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Channel {
+        Red,
+        Green,
+        Blue,
+        Alpha,
+    }
+
+    #[test]
+    fn par_new_inspect_order() {
+        let enum_map = EnumMap::<Channel, usize, { Channel::SIZE }>::par_new_inspect(|channel| {
+            channel.position() * 10
+        });
+        assert_eq!(0, enum_map[Channel::Red]);
+        assert_eq!(10, enum_map[Channel::Green]);
+        assert_eq!(20, enum_map[Channel::Blue]);
+        assert_eq!(30, enum_map[Channel::Alpha]);
+    }
+
+    #[test]
+    fn par_map_keys() {
+        let enum_map = EnumMap::<Channel, Vec<u8>, { Channel::SIZE }>::new(|| vec![1, 2, 3]);
+        let sums = enum_map.par_map(|channel, buffer| {
+            buffer.iter().map(|value| *value as usize).sum::<usize>() + channel.position()
+        });
+        assert_eq!(6, sums[Channel::Red]);
+        assert_eq!(9, sums[Channel::Alpha]);
+    }
+
+    #[test]
+    fn par_iter_kv_matches_sequential() {
+        let enum_map =
+            EnumMap::<Channel, usize, { Channel::SIZE }>::new_inspect(|channel| channel.position());
+        let parallel: Vec<(Channel, &usize)> = enum_map.par_iter_kv().collect();
+        let sequential: Vec<(Channel, &usize)> = enum_map.iter_kv().collect();
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn into_par_iter_references() {
+        let mut enum_map = EnumMap::<Channel, i32, { Channel::SIZE }>::new(|| 1);
+        (&mut enum_map)
+            .into_par_iter()
+            .for_each(|value| *value *= 2);
+        assert_eq!(8, (&enum_map).into_par_iter().sum::<i32>());
+    }
+}
@@ -0,0 +1,247 @@
+//! Implementation of [Enumerated] for enums that can't be annotated with `#[derive(Enumerated)]`.
+
+/// Implements [crate::Enumerated] for an enum declared elsewhere, e.g. in generated code or another crate,
+/// by listing its variants.
+///
+/// Variants are listed in the order of their positions, usually their order of declaration. Only fieldless
+/// enums are supported. The listed variants are checked against the enum: the generated `position` matches
+/// the variants exhaustively, so a variant missing from the list, a variant not present in the enum, or a
+/// variant with fields is a compile-time error.
+///
+/// `remote_enumerated!(path::Enum { ... })` implements the trait for the enum itself. Due to the orphan rule,
+/// this form only accepts enums defined in the crate invoking the macro.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated, remote_enumerated};
+///
+/// mod generated {
+///     #[derive(Clone, Copy, Debug, PartialEq)]
+///     pub enum Level {
+///         Info,
+///         Warn,
+///         Error,
+///     }
+/// }
+///
+/// remote_enumerated!(generated::Level { Info, Warn, Error });
+///
+/// assert_eq!(3, generated::Level::SIZE);
+/// assert_eq!(1, generated::Level::Warn.position());
+/// assert_eq!(generated::Level::Error, generated::Level::from_position(2));
+///
+/// let mut counts = EnumMap::<generated::Level, u32, { generated::Level::SIZE }>::new_default();
+/// counts[generated::Level::Warn] += 1;
+/// ```
+///
+/// Enums of other crates, e.g. `std::net::Shutdown` or `log::Level`, are wrapped in a newtype declared by
+/// the macro, `remote_enumerated!(pub struct Wrapper(path::Enum) { ... })`. The newtype implements the trait,
+/// its single field is public, and `From` converts between the newtype and the enum both ways.
+/// Attributes, e.g. derives, are applied to the newtype.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated, remote_enumerated};
+/// use std::net::Shutdown;
+///
+/// remote_enumerated!(
+///     #[derive(Clone, Copy, Debug, PartialEq)]
+///     pub struct ShutdownKey(Shutdown) { Read, Write, Both }
+/// );
+///
+/// let mut counts = EnumMap::<ShutdownKey, u32, { ShutdownKey::SIZE }>::new_default();
+/// counts[ShutdownKey::from(Shutdown::Write)] += 1;
+/// assert_eq!(1, counts[ShutdownKey(Shutdown::Write)]);
+/// assert_eq!(Shutdown::Both, Shutdown::from(ShutdownKey::from_position(2)));
+/// ```
+///
+/// Variants drifting from the enum fail to compile:
+/// ```compile_fail
+/// use enum_collections::remote_enumerated;
+///
+/// pub enum Level {
+///     Info,
+///     Warn,
+///     Error,
+/// }
+///
+/// remote_enumerated!(Level { Info, Warn }); // `Level::Error` not covered
+/// ```
+#[macro_export]
+macro_rules! remote_enumerated {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident($tp:path) { $($variant:ident),+ $(,)? }) => {
+        $(#[$meta])*
+        $vis struct $name(pub $tp);
+
+        impl From<$tp> for $name {
+            fn from(value: $tp) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $tp {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        $crate::__remote_enumerated!($name, $tp, |key| key.0, |value| $name(value), { $($variant),+ });
+    };
+    ($tp:path { $($variant:ident),+ $(,)? }) => {
+        $crate::__remote_enumerated!($tp, $tp, |key| *key, |value| value, { $($variant),+ });
+    };
+}
+
+/// Implementation of [remote_enumerated] for `$key`, mapping keys to the enum by `$unwrap` and back by `$wrap`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __remote_enumerated {
+    (
+        $key:ty, $tp:path, |$this:ident| $unwrap:expr, |$value:ident| $wrap:expr,
+        { $($variant:ident),+ }
+    ) => {
+        impl $crate::Enumerated for $key {
+            const SIZE: usize = <[&str]>::len(&[$(stringify!($variant)),+]);
+            $crate::__remote_variants!($tp, |$value| $wrap, { $($variant),+ });
+            const VARIANT_NAMES: &'static [&'static str] = &[$(stringify!($variant)),+];
+            const LAYOUT_HASH: u64 = $crate::__layout_hash(Self::VARIANT_NAMES);
+
+            fn position(&self) -> usize {
+                #[allow(non_camel_case_types, clippy::enum_variant_names)]
+                enum Position {
+                    $($variant),+
+                }
+                let $this = self;
+                match $unwrap {
+                    $(<$tp>::$variant => Position::$variant as usize),+
+                }
+            }
+
+            fn from_position(position: usize) -> Self {
+                #[allow(non_camel_case_types, clippy::enum_variant_names)]
+                enum Position {
+                    $($variant),+
+                }
+                $(
+                    if position == Position::$variant as usize {
+                        let $value = <$tp>::$variant;
+                        return $wrap;
+                    }
+                )+
+                panic!(
+                    "Position {} is out of range of `{}`",
+                    position,
+                    stringify!($key)
+                )
+            }
+        }
+    };
+}
+
+/// `VARIANTS` of [remote_enumerated], generated with the `variants` feature of this crate only.
+#[cfg(feature = "variants")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __remote_variants {
+    ($tp:path, |$value:ident| $wrap:expr, { $($variant:ident),+ }) => {
+        const VARIANTS: &'static [Self] = &[$({
+            let $value = <$tp>::$variant;
+            $wrap
+        }),+];
+    };
+}
+
+/// `VARIANTS` of [remote_enumerated], generated with the `variants` feature of this crate only.
+#[cfg(not(feature = "variants"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __remote_variants {
+    ($tp:path, |$value:ident| $wrap:expr, { $($variant:ident),+ }) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EnumMap, Enumerated, em, enumerated::layout_hash};
+
+    mod generated {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub enum Protocol {
+            Tcp,
+            Udp,
+            Quic,
+        }
+    }
+    use generated::Protocol;
+
+    remote_enumerated!(generated::Protocol { Tcp, Udp, Quic });
+
+    #[test]
+    fn positions() {
+        assert_eq!(3, Protocol::SIZE);
+        for (position, protocol) in [Protocol::Tcp, Protocol::Udp, Protocol::Quic]
+            .into_iter()
+            .enumerate()
+        {
+            assert_eq!(position, protocol.position());
+            assert_eq!(protocol, Protocol::from_position(position));
+        }
+        assert_eq!(&["Tcp", "Udp", "Quic"], Protocol::VARIANT_NAMES);
+        assert_eq!(layout_hash(&["Tcp", "Udp", "Quic"]), Protocol::LAYOUT_HASH);
+
+        let enum_map = em!(Protocol, u16, Tcp | Udp => 80, Quic => 443);
+        assert_eq!(443, enum_map[Protocol::Quic]);
+        let _: EnumMap<Protocol, u16, { Protocol::SIZE }> = enum_map;
+    }
+
+    #[test]
+    #[should_panic(expected = "Position 3 is out of range of `generated::Protocol`")]
+    fn from_position_out_of_range() {
+        Protocol::from_position(3);
+    }
+
+    #[cfg(feature = "variants")]
+    #[test]
+    fn variants() {
+        assert_eq!(
+            &[Protocol::Tcp, Protocol::Udp, Protocol::Quic],
+            Protocol::VARIANTS
+        );
+        assert_eq!(
+            &[
+                ShutdownKey(Shutdown::Read),
+                ShutdownKey(Shutdown::Write),
+                ShutdownKey(Shutdown::Both)
+            ],
+            ShutdownKey::VARIANTS
+        );
+    }
+
+    // A foreign enum, only the newtype form is accepted by the orphan rule
+    use std::net::Shutdown;
+
+    remote_enumerated!(
+        /// Key wrapping a standard library enum
+        #[derive(Debug, PartialEq)]
+        struct ShutdownKey(Shutdown) { Read, Write, Both }
+    );
+
+    #[test]
+    fn newtype() {
+        assert_eq!(3, ShutdownKey::SIZE);
+        assert_eq!(2, ShutdownKey(Shutdown::Both).position());
+        assert_eq!(ShutdownKey(Shutdown::Write), ShutdownKey::from_position(1));
+        assert_eq!(&["Read", "Write", "Both"], ShutdownKey::VARIANT_NAMES);
+        crate::check_enumerated::<ShutdownKey>();
+
+        // Not `Copy`, keys are borrowed
+        let key = ShutdownKey::from(Shutdown::Read);
+        let mut enum_map = EnumMap::<ShutdownKey, u8, { ShutdownKey::SIZE }>::new_default();
+        enum_map[&key] = 1;
+        assert_eq!(1, enum_map[&key]);
+        assert_eq!(Shutdown::Read, Shutdown::from(key));
+    }
+
+    #[test]
+    #[should_panic(expected = "Position 3 is out of range of `ShutdownKey`")]
+    fn newtype_from_position_out_of_range() {
+        ShutdownKey::from_position(3);
+    }
+}
@@ -0,0 +1,274 @@
+//! Zero-copy archiving of [EnumMap] using the `rkyv` crate.
+//!
+//! An EnumMap is archived as an [ArchivedEnumMap], an array of archived values indexable by the same keys.
+//! Archives can be accessed in place, e.g. from a memory-mapped file, without deserializing the map.
+
+use rkyv::{
+    Archive, Archived, Deserialize, Place, Portable, Serialize,
+    bytecheck::CheckBytes,
+    munge::munge,
+    rancor::{Fallible, Source},
+};
+use std::{fmt::Display, marker::PhantomData, ops::Index};
+
+use crate::{EnumMap, Enumerated, Keys, enummap::keys};
+
+/// Archived form of an [EnumMap], produced by rkyv. Indexable by the same keys as the original map.
+///
+/// The key's [Enumerated::LAYOUT_HASH] is archived alongside the values. When validated, the layout hash
+/// and the number of archived values are checked to match `K`, rejecting archives written before
+/// the variants of `K` changed.
+///
+/// ```
+/// use enum_collections::{ArchivedEnumMap, EnumMap, Enumerated};
+/// use rkyv::rancor::Error;
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let enum_map = EnumMap::<Letter, u32, { Letter::SIZE }>::new_inspect(|letter| match letter {
+///     Letter::A => 42,
+///     Letter::B => 24,
+/// });
+/// let bytes = rkyv::to_bytes::<Error>(&enum_map).unwrap();
+///
+/// let archived =
+///     rkyv::access::<ArchivedEnumMap<Letter, u32, { Letter::SIZE }>, Error>(&bytes).unwrap();
+/// assert_eq!(42, archived[Letter::A]);
+///
+/// let deserialized: EnumMap<Letter, u32, { Letter::SIZE }> =
+///     rkyv::deserialize::<_, Error>(archived).unwrap();
+/// assert_eq!(24, deserialized[Letter::B]);
+/// ```
+#[repr(C)]
+pub struct ArchivedEnumMap<K: Enumerated, V: Archive, const N: usize> {
+    layout_hash: Archived<u64>,
+    data: [Archived<V>; N],
+    _key: PhantomData<K>,
+}
+
+impl<K: Enumerated, V: Archive, const N: usize> ArchivedEnumMap<K, V, N> {
+    /// Iterates over the archived values, in the order of their variants' positions.
+    ///
+    /// ```
+    /// use enum_collections::{ArchivedEnumMap, EnumMap, Enumerated};
+    /// use rkyv::rancor::Error;
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Letter, u32, { Letter::SIZE }>::new(|| 42);
+    /// let bytes = rkyv::to_bytes::<Error>(&enum_map).unwrap();
+    /// let archived =
+    ///     rkyv::access::<ArchivedEnumMap<Letter, u32, { Letter::SIZE }>, Error>(&bytes).unwrap();
+    /// assert_eq!(84, archived.iter().map(|value| value.to_native()).sum::<u32>());
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, Archived<V>> {
+        self.data.iter()
+    }
+
+    /// Iterates over the variants and their archived values, in the order of their positions.
+    ///
+    /// ```
+    /// use enum_collections::{ArchivedEnumMap, EnumMap, Enumerated};
+    /// use rkyv::rancor::Error;
+    /// #[derive(Enumerated, Debug, PartialEq)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let enum_map = EnumMap::<Letter, u32, { Letter::SIZE }>::new_inspect(|letter| match letter {
+    ///     Letter::A => 1,
+    ///     Letter::B => 2,
+    /// });
+    /// let bytes = rkyv::to_bytes::<Error>(&enum_map).unwrap();
+    /// let archived =
+    ///     rkyv::access::<ArchivedEnumMap<Letter, u32, { Letter::SIZE }>, Error>(&bytes).unwrap();
+    /// let pairs: Vec<(Letter, u32)> = archived
+    ///     .iter_kv()
+    ///     .map(|(letter, value)| (letter, value.to_native()))
+    ///     .collect();
+    /// assert_eq!(vec![(Letter::A, 1), (Letter::B, 2)], pairs);
+    /// ```
+    pub fn iter_kv(&self) -> std::iter::Zip<Keys<K>, std::slice::Iter<'_, Archived<V>>> {
+        keys::<K>(N).zip(self.data.iter())
+    }
+}
+
+impl<K: Enumerated, V: Archive, const N: usize> Index<K> for ArchivedEnumMap<K, V, N> {
+    type Output = Archived<V>;
+
+    fn index(&self, key: K) -> &Self::Output {
+        &self.data[key.position()]
+    }
+}
+
+/// Allows indexing of ArchivedEnumMap by borrowed keys, for keys that aren't `Copy`.
+impl<K: Enumerated, V: Archive, const N: usize> Index<&K> for ArchivedEnumMap<K, V, N> {
+    type Output = Archived<V>;
+
+    fn index(&self, key: &K) -> &Self::Output {
+        &self.data[key.position()]
+    }
+}
+
+// SAFETY: ArchivedEnumMap is `#[repr(C)]`, its fields are portable, `PhantomData<K>` being zero-sized.
+unsafe impl<K: Enumerated, V: Archive, const N: usize> Portable for ArchivedEnumMap<K, V, N> {}
+
+impl<K: Enumerated, V: Archive, const N: usize> Archive for EnumMap<K, V, N> {
+    type Archived = ArchivedEnumMap<K, V, N>;
+    type Resolver = [V::Resolver; N];
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedEnumMap { layout_hash, data, .. } = out);
+        K::LAYOUT_HASH.resolve((), layout_hash);
+        self.data.resolve(resolver, data);
+    }
+}
+
+impl<K: Enumerated, V: Serialize<S>, S: Fallible + ?Sized, const N: usize> Serialize<S>
+    for EnumMap<K, V, N>
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.data.serialize(serializer)
+    }
+}
+
+impl<K: Enumerated, V: Archive, D: Fallible + ?Sized, const N: usize>
+    Deserialize<EnumMap<K, V, N>, D> for ArchivedEnumMap<K, V, N>
+where
+    Archived<V>: Deserialize<V, D>,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<EnumMap<K, V, N>, D::Error> {
+        Ok(EnumMap {
+            data: self.data.deserialize(deserializer)?,
+            _key: PhantomData,
+        })
+    }
+}
+
+/// Errors reported when an archived EnumMap doesn't match its key.
+#[derive(Debug)]
+enum ArchiveMismatch {
+    Length { expected: usize, actual: usize },
+    Layout { key: &'static str },
+}
+
+impl Display for ArchiveMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveMismatch::Length { expected, actual } => write!(
+                f,
+                "Archived EnumMap has {} values, but the key has {} variants",
+                actual, expected
+            ),
+            ArchiveMismatch::Layout { key } => write!(
+                f,
+                "EnumMap was archived with a different layout of `{}`, its variants have changed",
+                key
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveMismatch {}
+
+// SAFETY: The length is checked first, then the layout hash and the archived values, both by their own implementations.
+unsafe impl<K: Enumerated, V: Archive, C: Fallible + ?Sized, const N: usize> CheckBytes<C>
+    for ArchivedEnumMap<K, V, N>
+where
+    [Archived<V>; N]: CheckBytes<C>,
+    C::Error: Source,
+{
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        if N != K::SIZE {
+            return Err(C::Error::new(ArchiveMismatch::Length {
+                expected: K::SIZE,
+                actual: N,
+            }));
+        }
+        // SAFETY: The caller guarantees the pointer is aligned and points to enough bytes for Self,
+        // fields are therefore aligned and within bounds.
+        unsafe {
+            let layout_hash = &raw const (*value).layout_hash;
+            <Archived<u64>>::check_bytes(layout_hash, context)?;
+            if (*layout_hash).to_native() != K::LAYOUT_HASH {
+                return Err(C::Error::new(ArchiveMismatch::Layout {
+                    key: std::any::type_name::<K>(),
+                }));
+            }
+            <[Archived<V>; N]>::check_bytes(&raw const (*value).data, context)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use crate::{ArchivedEnumMap, EnumMap, Enumerated};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Region {
+        Europe,
+        Asia,
+        Americas,
+    }
+
+    #[test]
+    fn round_trip() {
+        let enum_map = EnumMap::<Region, String, { Region::SIZE }>::new_inspect(|region| {
+            format!("{:?}", region).to_lowercase()
+        });
+        let bytes = rkyv::to_bytes::<Error>(&enum_map).unwrap();
+
+        let archived =
+            rkyv::access::<ArchivedEnumMap<Region, String, { Region::SIZE }>, Error>(&bytes)
+                .unwrap();
+        assert_eq!("asia", archived[Region::Asia].as_str());
+        assert_eq!("europe", archived[&Region::Europe].as_str());
+        assert_eq!(3, archived.iter().count());
+
+        let deserialized: EnumMap<Region, String, { Region::SIZE }> =
+            rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(enum_map, deserialized);
+    }
+
+    #[test]
+    fn length_mismatch() {
+        // Four values archived, but the key only has three variants
+        let enum_map = EnumMap::<Region, u32, 4>::new_default();
+        let bytes = rkyv::to_bytes::<Error>(&enum_map).unwrap();
+        let error = rkyv::access::<ArchivedEnumMap<Region, u32, 4>, Error>(&bytes)
+            .err()
+            .unwrap();
+        assert!(
+            error
+                .to_string()
+                .contains("Archived EnumMap has 4 values, but the key has 3 variants")
+        );
+    }
+
+    #[test]
+    fn layout_mismatch() {
+        #[derive(Enumerated)]
+        enum Reordered {
+            Asia,
+            Europe,
+            Americas,
+        }
+
+        let enum_map = EnumMap::<Region, u32, { Region::SIZE }>::new_default();
+        let bytes = rkyv::to_bytes::<Error>(&enum_map).unwrap();
+        let error =
+            rkyv::access::<ArchivedEnumMap<Reordered, u32, { Reordered::SIZE }>, Error>(&bytes)
+                .err()
+                .unwrap();
+        assert!(error.to_string().contains("different layout"));
+    }
+}
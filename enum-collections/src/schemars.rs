@@ -0,0 +1,272 @@
+//! JSON Schema of [EnumMap] using the `schemars` crate.
+//!
+//! Describes the map form produced by the `serde` feature: an object with a property per variant of `K`.
+//! Variant names are taken from `K`'s own schema, values are described by `V`'s schema.
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+
+use crate::{EnumMap, Enumerated};
+
+/// Object with a property per variant of `K`, each described by `V`'s schema. Other properties are not allowed.
+///
+/// Properties are optional, as missing values are deserialized as `V::default()`.
+/// Use [EnumMap::json_schema_strict] to require all of them.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated};
+/// use schemars::JsonSchema;
+///
+/// #[derive(Enumerated, JsonSchema)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let schema = schemars::schema_for!(EnumMap<Letter, i32, { Letter::SIZE }>);
+/// assert_eq!(Some(false.into()), schema.get("additionalProperties").cloned());
+/// assert!(schema.pointer("/properties/A").is_some());
+/// assert!(schema.pointer("/properties/B").is_some());
+/// assert!(schema.get("required").is_none());
+/// ```
+impl<K: Enumerated + JsonSchema, V: JsonSchema, const N: usize> JsonSchema for EnumMap<K, V, N> {
+    fn schema_name() -> Cow<'static, str> {
+        format!("EnumMap_of_{}_and_{}", K::schema_name(), V::schema_name()).into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        format!(
+            "enum_collections::EnumMap<{}, {}>",
+            K::schema_id(),
+            V::schema_id()
+        )
+        .into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        enum_map_schema::<K, V>(generator, false)
+    }
+}
+
+impl<K: Enumerated + JsonSchema, V: JsonSchema, const N: usize> EnumMap<K, V, N> {
+    /// JSON Schema requiring a property for each variant of `K`. Usable with `#[schemars(schema_with = "...")]`
+    /// for maps that must be fully specified.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(Enumerated, JsonSchema)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// #[derive(JsonSchema)]
+    /// pub struct Config {
+    ///     #[schemars(schema_with = "EnumMap::<Letter, i32, { Letter::SIZE }>::json_schema_strict")]
+    ///     limits: EnumMap<Letter, i32, { Letter::SIZE }>,
+    /// }
+    ///
+    /// let schema = schemars::schema_for!(Config);
+    /// assert_eq!(
+    ///     Some(&serde_json::json!(["A", "B"])),
+    ///     schema.pointer("/properties/limits/required")
+    /// );
+    /// ```
+    pub fn json_schema_strict(generator: &mut SchemaGenerator) -> Schema {
+        enum_map_schema::<K, V>(generator, true)
+    }
+}
+
+fn enum_map_schema<K: JsonSchema, V: JsonSchema>(
+    generator: &mut SchemaGenerator,
+    required: bool,
+) -> Schema {
+    let value_schema = generator.subschema_for::<V>().to_value();
+    let key_schema = K::json_schema(generator);
+    match variant_names(&key_schema) {
+        Some(names) => {
+            let properties: Map<String, Value> = names
+                .iter()
+                .map(|name| (name.clone(), value_schema.clone()))
+                .collect();
+            let mut schema = json_schema!({
+                "type": "object",
+                "properties": properties,
+                "additionalProperties": false,
+            });
+            if required {
+                schema.insert("required".to_owned(), names.into());
+            }
+            schema
+        }
+        // Keys other than string enums, e.g. integers, constrain the property names by their string forms
+        None => {
+            let mut schema = json_schema!({
+                "type": "object",
+                "additionalProperties": value_schema,
+            });
+            if let Some(property_names) = property_names::<K>(generator, &key_schema) {
+                schema.insert("propertyNames".to_owned(), property_names);
+            }
+            schema
+        }
+    }
+}
+
+/// Schema of the property names keys are serialized as. JSON property names are always strings: string
+/// keys are described by their own schema, integers and booleans by their string forms. `None` for other keys,
+/// e.g. tuples, their string forms being unknown.
+fn property_names<K: JsonSchema>(
+    generator: &mut SchemaGenerator,
+    key_schema: &Schema,
+) -> Option<Value> {
+    match key_schema.get("type").and_then(Value::as_str)? {
+        "string" => Some(generator.subschema_for::<K>().to_value()),
+        "integer" => Some(serde_json::json!({
+            "type": "string",
+            "pattern": "^-?(0|[1-9][0-9]*)$",
+        })),
+        "boolean" => Some(serde_json::json!({
+            "type": "string",
+            "enum": ["false", "true"],
+        })),
+        _ => None,
+    }
+}
+
+/// Names of the variants of a string enum, described either by `enum`, or by `oneOf` of `const` subschemas.
+fn variant_names(schema: &Schema) -> Option<Vec<String>> {
+    fn strings(values: &[Value]) -> Option<Vec<String>> {
+        values
+            .iter()
+            .map(|value| value.as_str().map(str::to_owned))
+            .collect()
+    }
+
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return strings(values);
+    }
+    let subschemas = schema.get("oneOf").and_then(Value::as_array)?;
+    let mut names = Vec::new();
+    for subschema in subschemas {
+        if let Some(name) = subschema.get("const").and_then(Value::as_str) {
+            names.push(name.to_owned());
+        } else {
+            names.extend(strings(subschema.get("enum")?.as_array()?)?);
+        }
+    }
+    Some(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::{JsonSchema, json_schema, schema_for};
+
+    use crate::{EnumMap, Enumerated};
+
+    #[derive(Enumerated, JsonSchema)]
+    enum Region {
+        Europe,
+        Asia,
+    }
+
+    /// Documented variants are described with `oneOf`.
+    #[derive(Enumerated, JsonSchema)]
+    enum Documented {
+        /// First
+        First,
+        Second,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Limit {
+        max: u32,
+    }
+
+    #[test]
+    fn properties() {
+        let schema = schema_for!(EnumMap<Region, Limit, { Region::SIZE }>);
+        assert_eq!(
+            Some(&serde_json::json!({
+                "Europe": { "$ref": "#/$defs/Limit" },
+                "Asia": { "$ref": "#/$defs/Limit" },
+            })),
+            schema.get("properties")
+        );
+        assert!(schema.pointer("/$defs/Limit").is_some());
+    }
+
+    #[test]
+    fn documented_variants() {
+        let schema = schema_for!(EnumMap<Documented, u8, { Documented::SIZE }>);
+        assert!(schema.pointer("/properties/First").is_some());
+        assert!(schema.pointer("/properties/Second").is_some());
+    }
+
+    #[test]
+    fn strict() {
+        let mut generator = schemars::generate::SchemaSettings::default().into_generator();
+        let schema = EnumMap::<Region, bool, { Region::SIZE }>::json_schema_strict(&mut generator);
+        assert_eq!(
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "Europe": { "type": "boolean" },
+                    "Asia": { "type": "boolean" },
+                },
+                "additionalProperties": false,
+                "required": ["Europe", "Asia"],
+            }),
+            schema
+        );
+    }
+
+    /// Integer keys are serialized as strings of digits, never as JSON integers
+    #[cfg(feature = "ext")]
+    #[test]
+    fn integer_keys() {
+        let schema = schema_for!(EnumMap<u8, bool, { u8::SIZE }>);
+        assert_eq!(
+            Some(&serde_json::json!({
+                "type": "string",
+                "pattern": "^-?(0|[1-9][0-9]*)$",
+            })),
+            schema.get("propertyNames")
+        );
+        assert_eq!(
+            Some(&serde_json::json!({ "type": "boolean" })),
+            schema.get("additionalProperties")
+        );
+
+        let schema = schema_for!(EnumMap<bool, u8, { bool::SIZE }>);
+        assert_eq!(
+            Some(&serde_json::json!(["false", "true"])),
+            schema.pointer("/propertyNames/enum")
+        );
+
+        // The property names the schema describes
+        #[cfg(feature = "serde")]
+        {
+            let json =
+                serde_json::to_value(EnumMap::<u8, bool, { u8::SIZE }>::new_default()).unwrap();
+            assert_eq!(Some(&serde_json::json!(false)), json.get("255"));
+            let json =
+                serde_json::to_value(EnumMap::<bool, u8, { bool::SIZE }>::new_default()).unwrap();
+            assert_eq!(Some(&serde_json::json!(0)), json.get("false"));
+        }
+    }
+
+    /// Property names of keys without a string form are not constrained
+    #[cfg(feature = "ext")]
+    #[test]
+    fn composite_keys() {
+        type Key = (Region, bool);
+        let schema = schema_for!(EnumMap<Key, u8, { Key::SIZE }>);
+        assert_eq!(None, schema.get("propertyNames"));
+        assert_eq!(Some(&serde_json::json!("object")), schema.get("type"));
+    }
+}
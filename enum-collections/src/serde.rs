@@ -1,7 +1,11 @@
-use serde::{Deserialize, Serialize, de::Visitor};
-use std::{any::type_name, marker::PhantomData};
+use serde::{
+    Deserialize, Serialize,
+    de::{DeserializeOwned, IgnoredAny, IntoDeserializer, Visitor},
+    ser::SerializeMap,
+};
+use std::{any::type_name, collections::BTreeMap, marker::PhantomData};
 
-use crate::{EnumMap, Enumerated};
+use crate::{Bounded, EnumMap, Enumerated, SparseEnumMap};
 
 impl<K: Enumerated + Serialize, V: Serialize, const N: usize> Serialize for EnumMap<K, V, N> {
     #[inline]
@@ -74,3 +78,454 @@ impl<
         deserializer.deserialize_map(visitor)
     }
 }
+
+/// An EnumMap deserialized leniently: entries of keys unknown to `K`, e.g. variants added by a newer
+/// version of the writer, are collected into a side map instead of failing the whole document.
+/// Serializing writes the unknown entries back after the known ones, as string keys, so they survive
+/// a round trip through formats with string keys, such as JSON.
+///
+/// Unknown keys are recognized by their names. Keys must therefore be deserializable from a string,
+/// which is the case for enums with unit variants, including variants renamed with `#[serde(alias = "...")]`.
+/// Only names `K` reports as unknown variants are unknown. Keys failing for other reasons, e.g. integer keys
+/// such as `u8` or [Bounded], which can't be deserialized from a string, fail the whole document.
+///
+/// ```
+/// use enum_collections::{EnumMap, EnumMapWithUnknown, Enumerated};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Enumerated, Serialize, Deserialize, Debug, PartialEq)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let with_unknown: EnumMapWithUnknown<Letter, i32, { Letter::SIZE }> =
+///     serde_json::from_str(r#"{"A": 1, "C": 3}"#).unwrap();
+/// assert_eq!(1, with_unknown.known[Letter::A]);
+/// assert_eq!(0, with_unknown.known[Letter::B]);
+/// assert_eq!(Some(&3), with_unknown.unknown.get("C"));
+///
+/// let serialized = serde_json::to_string(&with_unknown).unwrap();
+/// assert_eq!(r#"{"A":1,"B":0,"C":3}"#, serialized);
+/// assert_eq!(with_unknown, serde_json::from_str(&serialized).unwrap());
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[cfg_attr(feature = "eq", derive(PartialEq, Eq))]
+pub struct EnumMapWithUnknown<K: Enumerated, V, const N: usize> {
+    /// Values of the keys known to `K`. Keys missing from the document hold `V::default()`.
+    pub known: EnumMap<K, V, N>,
+    /// Values of the keys unknown to `K`, by the keys' names.
+    pub unknown: BTreeMap<String, V>,
+}
+
+impl<K: Enumerated + DeserializeOwned, V, const N: usize> EnumMap<K, V, N> {
+    /// Deserializes an EnumMap, skipping entries of keys unknown to `K` instead of failing.
+    /// Values of skipped entries are not required to be valid `V`s.
+    ///
+    /// Meant to be used with `#[serde(deserialize_with = "EnumMap::deserialize_ignore_unknown")]`.
+    /// Keys must be deserializable from a string, see [EnumMapWithUnknown]. The `Deserialize`
+    /// implementation of EnumMap itself fails on unknown keys.
+    ///
+    /// ```
+    /// use enum_collections::{EnumMap, Enumerated};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Enumerated, Deserialize)]
+    /// pub enum Letter {
+    ///    A,
+    ///    #[serde(alias = "Bee")]
+    ///    B,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// pub struct Config {
+    ///     #[serde(deserialize_with = "EnumMap::deserialize_ignore_unknown")]
+    ///     limits: EnumMap<Letter, i32, { Letter::SIZE }>,
+    /// }
+    ///
+    /// let config: Config = ron::from_str("(limits: {A: 1, Bee: 2, C: \"unknown\"})").unwrap();
+    /// assert_eq!(1, config.limits[Letter::A]);
+    /// assert_eq!(2, config.limits[Letter::B]);
+    /// ```
+    pub fn deserialize_ignore_unknown<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        V: Deserialize<'de> + Default,
+    {
+        let visitor = LenientVisitor::<K, V, N> {
+            collect_unknown: false,
+            marker: PhantomData,
+        };
+        deserializer
+            .deserialize_map(visitor)
+            .map(|with_unknown| with_unknown.known)
+    }
+}
+
+impl<K: Enumerated + Serialize, V: Serialize, const N: usize> Serialize
+    for EnumMapWithUnknown<K, V, N>
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(N + self.unknown.len()))?;
+        for (key, value) in self.known.iter_kv() {
+            map.serialize_entry(&key, value)?;
+        }
+        for (key, value) in &self.unknown {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, K: Enumerated + DeserializeOwned, V: Deserialize<'de> + Default, const N: usize>
+    Deserialize<'de> for EnumMapWithUnknown<K, V, N>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let visitor = LenientVisitor {
+            collect_unknown: true,
+            marker: PhantomData,
+        };
+        deserializer.deserialize_map(visitor)
+    }
+}
+
+/// Name of a map's key, read as an identifier to be resolved into a variant afterwards.
+struct KeyName(String);
+
+impl<'de> Deserialize<'de> for KeyName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct KeyNameVisitor;
+
+        impl Visitor<'_> for KeyNameVisitor {
+            type Value = KeyName;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "name of a key")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(KeyName(v.to_owned()))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(KeyName(v))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                String::from_utf8(v.to_vec())
+                    .map(KeyName)
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Bytes(v), &self))
+            }
+        }
+
+        deserializer.deserialize_identifier(KeyNameVisitor)
+    }
+}
+
+/// Failure to resolve a key's name into a variant, telling unknown variants apart from other failures.
+#[derive(Debug)]
+enum KeyError {
+    UnknownVariant,
+    Other(String),
+}
+
+impl std::fmt::Display for KeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyError::UnknownVariant => write!(f, "unknown variant"),
+            KeyError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+impl serde::de::Error for KeyError {
+    fn custom<T: std::fmt::Display>(message: T) -> Self {
+        KeyError::Other(message.to_string())
+    }
+
+    fn unknown_variant(_variant: &str, _expected: &'static [&'static str]) -> Self {
+        KeyError::UnknownVariant
+    }
+}
+
+/// Visits maps whose keys might be unknown to `K`, either collecting or skipping their entries.
+struct LenientVisitor<K, V, const N: usize> {
+    collect_unknown: bool,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<'de, K: Enumerated + DeserializeOwned, V: Deserialize<'de> + Default, const N: usize>
+    Visitor<'de> for LenientVisitor<K, V, N>
+{
+    type Value = EnumMapWithUnknown<K, V, N>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "EnumMap<{}, {}>",
+            type_name::<K>(),
+            type_name::<V>()
+        )
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut data = std::array::from_fn(|_| V::default());
+        let mut unknown = BTreeMap::new();
+        while let Some(KeyName(name)) = map.next_key::<KeyName>()? {
+            match K::deserialize(name.as_str().into_deserializer()) {
+                Ok(key) => data[key.position()] = map.next_value()?,
+                Err(KeyError::UnknownVariant) if self.collect_unknown => {
+                    unknown.insert(name, map.next_value()?);
+                }
+                Err(KeyError::UnknownVariant) => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+                Err(KeyError::Other(message)) => {
+                    return Err(serde::de::Error::custom(format_args!(
+                        "invalid key `{}`: {}",
+                        name, message
+                    )));
+                }
+            }
+        }
+
+        Ok(EnumMapWithUnknown {
+            known: EnumMap {
+                data,
+                _key: PhantomData,
+            },
+            unknown,
+        })
+    }
+}
+
+/// Serializes the present entries only, as a map.
+impl<K: Enumerated + Serialize, V: Serialize> Serialize for SparseEnumMap<K, V> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.iter_kv())
+    }
+}
+
+struct SparseEnumMapVisitor<K, V> {
+    marker: PhantomData<(K, V)>,
+}
+
+impl<'de, K: Enumerated + Deserialize<'de>, V: Deserialize<'de>> Visitor<'de>
+    for SparseEnumMapVisitor<K, V>
+{
+    type Value = SparseEnumMap<K, V>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "SparseEnumMap<{}, {}>",
+            type_name::<K>(),
+            type_name::<V>()
+        )
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut sparse_map = SparseEnumMap::new();
+        while let Some((k, v)) = map.next_entry::<K, V>()? {
+            sparse_map.insert(k, v);
+        }
+        Ok(sparse_map)
+    }
+}
+
+/// Deserializes a map of the present entries, the same format as EnumMap's, keys missing from the document
+/// being absent.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated, SparseEnumMap};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Enumerated, Serialize, Deserialize, Debug, PartialEq)]
+/// pub enum Letter {
+///    A,
+///    B,
+///    C,
+/// }
+///
+/// let sparse_map: SparseEnumMap<Letter, i32> = serde_json::from_str(r#"{"C": 3, "A": 1}"#).unwrap();
+/// assert_eq!(None, sparse_map.get(&Letter::B));
+/// assert_eq!(r#"{"A":1,"C":3}"#, serde_json::to_string(&sparse_map).unwrap());
+///
+/// // Readable as a dense map as well
+/// let enum_map: EnumMap<Letter, i32, { Letter::SIZE }> = serde_json::from_str(r#"{"A":1,"C":3}"#).unwrap();
+/// assert_eq!(0, enum_map[Letter::B]);
+/// ```
+impl<'de, K: Enumerated + Deserialize<'de>, V: Deserialize<'de>> Deserialize<'de>
+    for SparseEnumMap<K, V>
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let visitor = SparseEnumMapVisitor {
+            marker: PhantomData,
+        };
+        deserializer.deserialize_map(visitor)
+    }
+}
+
+/// Serializes the plain integer.
+impl<const LO: i64, const HI: i64> Serialize for Bounded<LO, HI> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i64(self.get())
+    }
+}
+
+/// Deserializes a plain integer, failing for values not within `LO..=HI`.
+///
+/// ```
+/// use enum_collections::{Bounded, EnumMap, Enumerated};
+///
+/// type Hour = Bounded<0, 23>;
+///
+/// let enum_map: EnumMap<Hour, u32, { Hour::SIZE }> = serde_json::from_str(r#"{"7": 1, "19": 2}"#).unwrap();
+/// assert_eq!(2, enum_map[Hour::new(19).unwrap()]);
+/// assert!(serde_json::from_str::<Hour>("24").is_err());
+/// ```
+impl<'de, const LO: i64, const HI: i64> Deserialize<'de> for Bounded<LO, HI> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = i64::deserialize(deserializer)?;
+        Self::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{Bounded, EnumMap, EnumMapWithUnknown, Enumerated, SparseEnumMap};
+
+    #[derive(Enumerated, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    enum Region {
+        Europe,
+        #[serde(alias = "Orient")]
+        Asia,
+    }
+
+    #[test]
+    fn unknown_key_fails_by_default() {
+        let result: Result<EnumMap<Region, u32, { Region::SIZE }>, _> =
+            serde_json::from_str(r#"{"Europe": 1, "Africa": 2}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn ignore_unknown() {
+        let mut deserializer =
+            serde_json::Deserializer::from_str(r#"{"Africa": [1, 2], "Orient": 2, "Europe": 1}"#);
+        let enum_map =
+            EnumMap::<Region, u32, { Region::SIZE }>::deserialize_ignore_unknown(&mut deserializer)
+                .unwrap();
+        assert_eq!(1, enum_map[Region::Europe]);
+        assert_eq!(2, enum_map[Region::Asia]);
+    }
+
+    #[test]
+    fn collect_unknown() {
+        let json = r#"{"Europe": 1, "Africa": 3, "Oceania": 4}"#;
+        let with_unknown: EnumMapWithUnknown<Region, u32, { Region::SIZE }> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(1, with_unknown.known[Region::Europe]);
+        assert_eq!(0, with_unknown.known[Region::Asia]);
+        assert_eq!(2, with_unknown.unknown.len());
+        assert_eq!(Some(&4), with_unknown.unknown.get("Oceania"));
+
+        let serialized = serde_json::to_string(&with_unknown).unwrap();
+        assert_eq!(
+            r#"{"Europe":1,"Asia":0,"Africa":3,"Oceania":4}"#,
+            serialized
+        );
+    }
+
+    /// Keys that can't be deserialized from their names fail instead of being taken for unknown ones
+    #[test]
+    fn lenient_non_string_keys() {
+        type Key = Bounded<0, 2>;
+        let json = r#"{"0": 1, "1": 2, "2": 3}"#;
+        let strict: EnumMap<Key, u32, { Key::SIZE }> = serde_json::from_str(json).unwrap();
+        assert_eq!(vec![&1, &2, &3], strict.iter().collect::<Vec<_>>());
+
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let error =
+            EnumMap::<Key, u32, { Key::SIZE }>::deserialize_ignore_unknown(&mut deserializer)
+                .unwrap_err();
+        assert!(error.to_string().contains("invalid key `0`"), "{}", error);
+
+        let result: Result<EnumMapWithUnknown<Key, u32, { Key::SIZE }>, _> =
+            serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn alias() {
+        let enum_map: EnumMap<Region, u32, { Region::SIZE }> =
+            serde_json::from_str(r#"{"Orient": 7}"#).unwrap();
+        assert_eq!(7, enum_map[Region::Asia]);
+    }
+
+    #[test]
+    fn bounded() {
+        type Priority = Bounded<0, 7>;
+        let priority = Priority::new(3).unwrap();
+        assert_eq!("3", serde_json::to_string(&priority).unwrap());
+        assert_eq!(priority, serde_json::from_str("3").unwrap());
+
+        let error = serde_json::from_str::<Priority>("8").unwrap_err();
+        assert!(error.to_string().contains("Value 8 is out of range 0..=7"));
+
+        let mut enum_map = EnumMap::<Priority, bool, { Priority::SIZE }>::new_default();
+        enum_map[priority] = true;
+        let serialized = ron::to_string(&enum_map).unwrap();
+        let deserialized: EnumMap<Priority, bool, { Priority::SIZE }> =
+            ron::from_str(&serialized).unwrap();
+        assert_eq!(enum_map, deserialized);
+    }
+
+    #[test]
+    fn sparse() {
+        let mut sparse_map = SparseEnumMap::<Region, u32>::new();
+        sparse_map.insert(Region::Asia, 2);
+        let serialized = ron::to_string(&sparse_map).unwrap();
+        assert_eq!("{Asia:2}", serialized);
+        let deserialized: SparseEnumMap<Region, u32> = ron::from_str(&serialized).unwrap();
+        assert_eq!(sparse_map, deserialized);
+
+        let result: Result<SparseEnumMap<Region, u32>, _> =
+            serde_json::from_str(r#"{"Africa": 2}"#);
+        assert!(result.is_err());
+    }
+}
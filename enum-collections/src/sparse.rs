@@ -0,0 +1,705 @@
+//! Map of enum variants to values, storing only the values present.
+
+use std::{
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use crate::{EnumMap, Enumerated, bitmap::Bitmap};
+
+/// A map of enum variants to values, for large enums with few values present.
+///
+/// Unlike [EnumMap], which holds a value for each variant, a SparseEnumMap holds a bit per variant marking
+/// its presence, and the present values only, in the order of their variants' positions.
+/// A map of a 3000-variant enum with a handful of entries takes a few hundred bytes.
+///
+/// Lookups count the present values preceding the key, `O(K::SIZE / 64)`. Insertions and removals
+/// shift the values following the key. For small enums or densely populated maps, prefer [EnumMap].
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated, SparseEnumMap};
+///
+/// #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+/// pub enum Opcode {
+///     Nop,
+///     Load,
+///     Store,
+///     Jump,
+/// }
+///
+/// let mut counts = SparseEnumMap::<Opcode, u32>::new();
+/// counts.insert(Opcode::Jump, 2);
+/// *counts.get_or_insert_with(Opcode::Load, || 0) += 1;
+/// assert_eq!(Some(&2), counts.get(&Opcode::Jump));
+/// assert_eq!(None, counts.get(&Opcode::Store));
+/// assert_eq!(1, counts[Opcode::Load]);
+/// assert_eq!(
+///     vec![(Opcode::Load, &1), (Opcode::Jump, &2)],
+///     counts.iter_kv().collect::<Vec<_>>()
+/// );
+///
+/// // Conversions to and from dense maps of optional values
+/// let dense: EnumMap<Opcode, Option<u32>, { Opcode::SIZE }> = counts.clone().into();
+/// assert_eq!(Some(2), dense[Opcode::Jump]);
+/// assert_eq!(counts, SparseEnumMap::from(dense));
+/// ```
+pub struct SparseEnumMap<K: Enumerated, V> {
+    present: Bitmap,
+    values: Vec<V>,
+    _key: PhantomData<K>,
+}
+
+impl<K: Enumerated, V> SparseEnumMap<K, V> {
+    /// Creates an empty SparseEnumMap.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// assert!(sparse_map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            present: Bitmap::new(K::SIZE),
+            values: Vec::new(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Index of the key's value in `values`, whether present or not.
+    fn index(&self, position: usize) -> usize {
+        self.present.rank(position)
+    }
+
+    /// Value of the given key, `None` if not present.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::B, 42);
+    /// assert_eq!(None, sparse_map.get(&Letter::A));
+    /// assert_eq!(Some(&42), sparse_map.get(&Letter::B));
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let position = key.position();
+        if self.present.contains(position) {
+            Some(&self.values[self.index(position)])
+        } else {
+            None
+        }
+    }
+
+    /// Mutable reference to the value of the given key, `None` if not present.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::B, 42);
+    /// if let Some(value) = sparse_map.get_mut(&Letter::B) {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(Some(&43), sparse_map.get(&Letter::B));
+    /// ```
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let position = key.position();
+        if self.present.contains(position) {
+            let index = self.index(position);
+            Some(&mut self.values[index])
+        } else {
+            None
+        }
+    }
+
+    /// Inserts a value for the given key. Returns the previous value, if present.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// assert_eq!(None, sparse_map.insert(Letter::A, 1));
+    /// assert_eq!(Some(1), sparse_map.insert(Letter::A, 2));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let position = key.position();
+        let index = self.index(position);
+        if self.present.insert(position) {
+            self.values.insert(index, value);
+            None
+        } else {
+            Some(std::mem::replace(&mut self.values[index], value))
+        }
+    }
+
+    /// Removes the value of the given key. Returns the removed value, if present.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::A, 1);
+    /// assert_eq!(Some(1), sparse_map.remove(&Letter::A));
+    /// assert_eq!(None, sparse_map.remove(&Letter::A));
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let position = key.position();
+        if self.present.remove(position) {
+            Some(self.values.remove(self.index(position)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if a value of the given key is present.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::A, 1);
+    /// assert!(sparse_map.contains_key(&Letter::A));
+    /// assert!(!sparse_map.contains_key(&Letter::B));
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.present.contains(key.position())
+    }
+
+    /// Returns the value of the given key, inserting the one provided by `value_provider` if not present.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// *sparse_map.get_or_insert_with(Letter::A, || 40) += 2;
+    /// assert_eq!(Some(&42), sparse_map.get(&Letter::A));
+    /// ```
+    pub fn get_or_insert_with(&mut self, key: K, value_provider: impl FnOnce() -> V) -> &mut V {
+        let position = key.position();
+        let index = self.index(position);
+        if self.present.insert(position) {
+            self.values.insert(index, value_provider());
+        }
+        &mut self.values[index]
+    }
+
+    /// Number of values present.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::B, 1);
+    /// assert_eq!(1, sparse_map.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no value is present.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// assert!(sparse_map.is_empty());
+    /// sparse_map.insert(Letter::B, 1);
+    /// assert!(!sparse_map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Removes all values.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::B, 1);
+    /// sparse_map.clear();
+    /// assert!(sparse_map.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.present.clear();
+        self.values.clear();
+    }
+
+    /// Iterates over the present keys and their values, in the order of the keys' positions.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated, Debug, PartialEq)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    ///    C,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::C, 3);
+    /// sparse_map.insert(Letter::A, 1);
+    /// assert_eq!(
+    ///     vec![(Letter::A, &1), (Letter::C, &3)],
+    ///     sparse_map.iter_kv().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn iter_kv(&self) -> impl Iterator<Item = (K, &V)> {
+        self.present
+            .iter()
+            .map(K::from_position)
+            .zip(self.values.iter())
+    }
+
+    /// Mutably iterates over the present keys and their values, in the order of the keys' positions.
+    /// Only the values are mutable.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::B, 1);
+    /// for (_letter, value) in sparse_map.iter_kv_mut() {
+    ///     *value *= 10;
+    /// }
+    /// assert_eq!(Some(&10), sparse_map.get(&Letter::B));
+    /// ```
+    pub fn iter_kv_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> {
+        self.present
+            .iter()
+            .map(K::from_position)
+            .zip(self.values.iter_mut())
+    }
+
+    /// Iterates over the present values, in the order of their keys' positions.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::B, 2);
+    /// sparse_map.insert(Letter::A, 1);
+    /// assert_eq!(vec![&1, &2], sparse_map.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Mutably iterates over the present values, in the order of their keys' positions.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// pub enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+    /// sparse_map.insert(Letter::B, 2);
+    /// sparse_map.iter_mut().for_each(|value| *value += 1);
+    /// assert_eq!(Some(&3), sparse_map.get(&Letter::B));
+    /// ```
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+}
+
+impl<K: Enumerated, V> Default for SparseEnumMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Clones the SparseEnumMap by cloning each present value.
+impl<K: Enumerated, V: Clone> Clone for SparseEnumMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            present: self.present.clone(),
+            values: self.values.clone(),
+            _key: PhantomData,
+        }
+    }
+}
+
+/// Indexes present values.
+///
+/// # Panics
+///
+/// Panics if no value of the key is present. Use [SparseEnumMap::get] to look up keys that may be absent.
+///
+/// ```
+/// use enum_collections::{Enumerated, SparseEnumMap};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+/// sparse_map.insert(Letter::B, 42);
+/// assert_eq!(42, sparse_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V> Index<K> for SparseEnumMap<K, V> {
+    type Output = V;
+
+    fn index(&self, key: K) -> &Self::Output {
+        &self[&key]
+    }
+}
+
+/// Indexes present values by borrowed keys.
+///
+/// # Panics
+///
+/// Panics if no value of the key is present.
+impl<K: Enumerated, V> Index<&K> for SparseEnumMap<K, V> {
+    type Output = V;
+
+    fn index(&self, key: &K) -> &Self::Output {
+        let position = key.position();
+        self.get(key).unwrap_or_else(|| {
+            panic!(
+                "No value present at position {} of `{}`",
+                position,
+                std::any::type_name::<K>()
+            )
+        })
+    }
+}
+
+/// Mutably indexes present values.
+///
+/// # Panics
+///
+/// Panics if no value of the key is present. Use [SparseEnumMap::insert] to add values.
+///
+/// ```
+/// use enum_collections::{Enumerated, SparseEnumMap};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+/// sparse_map.insert(Letter::B, 42);
+/// sparse_map[Letter::B] += 1;
+/// assert_eq!(43, sparse_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V> IndexMut<K> for SparseEnumMap<K, V> {
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        &mut self[&key]
+    }
+}
+
+/// Mutably indexes present values by borrowed keys.
+///
+/// # Panics
+///
+/// Panics if no value of the key is present.
+impl<K: Enumerated, V> IndexMut<&K> for SparseEnumMap<K, V> {
+    fn index_mut(&mut self, key: &K) -> &mut Self::Output {
+        let position = key.position();
+        self.get_mut(key).unwrap_or_else(|| {
+            panic!(
+                "No value present at position {} of `{}`",
+                position,
+                std::any::type_name::<K>()
+            )
+        })
+    }
+}
+
+/// Collects key-value pairs, the last value of a key repeated wins.
+///
+/// ```
+/// use enum_collections::{Enumerated, SparseEnumMap};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let sparse_map: SparseEnumMap<Letter, i32> = [(Letter::B, 1), (Letter::B, 2)].into_iter().collect();
+/// assert_eq!(1, sparse_map.len());
+/// assert_eq!(2, sparse_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V> FromIterator<(K, V)> for SparseEnumMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut sparse_map = Self::new();
+        for (key, value) in iter {
+            sparse_map.insert(key, value);
+        }
+        sparse_map
+    }
+}
+
+/// Keeps the `Some` values of a dense EnumMap.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated, SparseEnumMap};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut enum_map = EnumMap::<Letter, Option<i32>, { Letter::SIZE }>::new_option();
+/// enum_map[Letter::B] = Some(42);
+/// let sparse_map = SparseEnumMap::from(enum_map);
+/// assert_eq!(1, sparse_map.len());
+/// assert_eq!(42, sparse_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V, const N: usize> From<EnumMap<K, Option<V>, N>> for SparseEnumMap<K, V> {
+    fn from(enum_map: EnumMap<K, Option<V>, N>) -> Self {
+        assert!(
+            N == K::SIZE,
+            "EnumMap of length {} doesn't match the {} keys",
+            N,
+            K::SIZE
+        );
+        let mut present = Bitmap::new(K::SIZE);
+        let mut values = Vec::new();
+        for (position, value) in enum_map.data.into_iter().enumerate() {
+            if let Some(value) = value {
+                present.insert(position);
+                values.push(value);
+            }
+        }
+        Self {
+            present,
+            values,
+            _key: PhantomData,
+        }
+    }
+}
+
+/// Dense EnumMap of optional values, `None` for keys not present.
+///
+/// ```
+/// use enum_collections::{EnumMap, Enumerated, SparseEnumMap};
+/// #[derive(Enumerated)]
+/// pub enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+/// sparse_map.insert(Letter::B, 42);
+/// let enum_map: EnumMap<Letter, Option<i32>, { Letter::SIZE }> = sparse_map.into();
+/// assert_eq!(None, enum_map[Letter::A]);
+/// assert_eq!(Some(42), enum_map[Letter::B]);
+/// ```
+impl<K: Enumerated, V, const N: usize> From<SparseEnumMap<K, V>> for EnumMap<K, Option<V>, N> {
+    fn from(sparse_map: SparseEnumMap<K, V>) -> Self {
+        assert!(
+            N == K::SIZE,
+            "EnumMap of length {} doesn't match the {} keys",
+            N,
+            K::SIZE
+        );
+        let mut enum_map = EnumMap::new_option();
+        for (position, value) in sparse_map.present.iter().zip(sparse_map.values) {
+            enum_map.data[position] = Some(value);
+        }
+        enum_map
+    }
+}
+
+/// Prints the present keys and their values as a map.
+///
+/// ```
+/// use enum_collections::{Enumerated, SparseEnumMap};
+/// #[derive(Enumerated, Debug)]
+/// enum Letter {
+///    A,
+///    B,
+/// }
+///
+/// let mut sparse_map = SparseEnumMap::<Letter, i32>::new();
+/// sparse_map.insert(Letter::B, 42);
+/// assert_eq!("{B: 42}", format!("{:?}", sparse_map));
+/// ```
+#[cfg(feature = "debug")]
+impl<K: Enumerated + std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for SparseEnumMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter_kv()).finish()
+    }
+}
+
+#[cfg(feature = "eq")]
+mod eq {
+    use super::{Enumerated, SparseEnumMap};
+
+    /// Two sparse maps are equal if the same keys are present, with equal values.
+    ///
+    /// ```
+    /// use enum_collections::{Enumerated, SparseEnumMap};
+    /// #[derive(Enumerated)]
+    /// enum Letter {
+    ///    A,
+    ///    B,
+    /// }
+    ///
+    /// let first: SparseEnumMap<Letter, i32> = [(Letter::A, 1)].into_iter().collect();
+    /// let second: SparseEnumMap<Letter, i32> = [(Letter::A, 1)].into_iter().collect();
+    /// let third: SparseEnumMap<Letter, i32> = [(Letter::B, 1)].into_iter().collect();
+    /// assert!(first == second);
+    /// assert!(first != third);
+    /// ```
+    impl<K: Enumerated, V: PartialEq> PartialEq for SparseEnumMap<K, V> {
+        fn eq(&self, other: &Self) -> bool {
+            self.present == other.present && self.values == other.values
+        }
+    }
+
+    impl<K: Enumerated, V: Eq> Eq for SparseEnumMap<K, V> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EnumMap, Enumerated, SparseEnumMap};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Digit {
+        D0,
+        D1,
+        D2,
+        D3,
+        D4,
+        D5,
+        D6,
+        D7,
+        D8,
+        D9,
+    }
+
+    /// A thousand variants, spanning several bitmap words
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    struct Code(Digit, Digit, Digit);
+
+    #[test]
+    fn insert_and_remove_in_any_order() {
+        let codes = [
+            Code(Digit::D9, Digit::D9, Digit::D9),
+            Code(Digit::D0, Digit::D0, Digit::D0),
+            Code(Digit::D5, Digit::D1, Digit::D2),
+            Code(Digit::D0, Digit::D6, Digit::D4),
+        ];
+        let mut sparse_map = SparseEnumMap::<Code, usize>::new();
+        for code in codes {
+            assert_eq!(None, sparse_map.insert(code, code.position()));
+        }
+        assert_eq!(4, sparse_map.len());
+        for code in codes {
+            assert_eq!(Some(&code.position()), sparse_map.get(&code));
+        }
+        let positions: Vec<usize> = sparse_map
+            .iter_kv()
+            .map(|(code, _)| code.position())
+            .collect();
+        assert_eq!(vec![0, 64, 512, 999], positions);
+
+        assert_eq!(Some(512), sparse_map.remove(&codes[2]));
+        assert_eq!(None, sparse_map.get(&codes[2]));
+        assert_eq!(Some(&999), sparse_map.get(&codes[0]));
+        assert_eq!(vec![&0, &64, &999], sparse_map.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn dense_round_trip() {
+        let mut enum_map = EnumMap::<Code, Option<u8>, { Code::SIZE }>::new_option();
+        enum_map[Code(Digit::D1, Digit::D2, Digit::D3)] = Some(1);
+        enum_map[Code(Digit::D7, Digit::D0, Digit::D0)] = Some(7);
+
+        let sparse_map = SparseEnumMap::from(enum_map);
+        assert_eq!(2, sparse_map.len());
+        assert_eq!(7, sparse_map[Code(Digit::D7, Digit::D0, Digit::D0)]);
+
+        let dense: EnumMap<Code, Option<u8>, { Code::SIZE }> = sparse_map.into();
+        // Compared by values, `PartialEq` requires the `eq` feature
+        assert!(enum_map.iter().eq(dense.iter()));
+    }
+
+    #[test]
+    #[should_panic(expected = "No value present at position 3")]
+    fn index_absent() {
+        let sparse_map = SparseEnumMap::<Digit, u8>::new();
+        let _ = sparse_map[Digit::D3];
+    }
+
+    #[test]
+    #[should_panic(expected = "EnumMap of length 11 doesn't match the 10 keys")]
+    fn from_longer_enum_map() {
+        let enum_map = EnumMap::<Digit, Option<u8>, 11>::new_option();
+        let _ = SparseEnumMap::from(enum_map);
+    }
+
+    #[test]
+    #[should_panic(expected = "EnumMap of length 9 doesn't match the 10 keys")]
+    fn into_shorter_enum_map() {
+        let sparse_map = SparseEnumMap::<Digit, u8>::new();
+        let _: EnumMap<Digit, Option<u8>, 9> = sparse_map.into();
+    }
+}
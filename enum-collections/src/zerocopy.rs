@@ -0,0 +1,47 @@
+//! Zero-copy conversions of [crate::EnumMap] from and to bytes using the `zerocopy` crate.
+//!
+//! `FromBytes`, `IntoBytes`, `KnownLayout` and `Immutable` are derived on EnumMap itself,
+//! relying on its `#[repr(transparent)]` layout over `[V; N]`. Use the traits' methods,
+//! such as `FromBytes::ref_from_bytes` and `IntoBytes::as_bytes`, to convert.
+
+#[cfg(test)]
+mod tests {
+    use zerocopy::{FromBytes, FromZeros, IntoBytes};
+
+    use crate::{EnumMap, Enumerated};
+
+    #[derive(Enumerated, Clone, Copy, Debug, PartialEq)]
+    enum Counter {
+        Hits,
+        Misses,
+        Evictions,
+    }
+
+    type Counters = EnumMap<Counter, u32, { Counter::SIZE }>;
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut counters = Counters::new_zeroed();
+        counters[Counter::Misses] = 7;
+
+        let bytes = IntoBytes::as_bytes(&counters);
+        assert_eq!(12, bytes.len());
+        assert_eq!(&7u32.to_ne_bytes(), &bytes[4..8]);
+
+        let read = Counters::read_from_bytes(bytes).unwrap();
+        assert_eq!(counters, read);
+        assert!(Counters::read_from_bytes(&bytes[..8]).is_err());
+    }
+
+    #[test]
+    fn cast_in_place() {
+        let mut buffer = [1u32, 2, 3];
+        let counters = Counters::mut_from_bytes(buffer.as_mut_bytes()).unwrap();
+        counters[Counter::Evictions] = 42;
+        assert_eq!([1, 2, 42], buffer);
+
+        let buffers = [[1u32, 2, 3], [4, 5, 6]];
+        let counters = <[Counters]>::ref_from_bytes(buffers.as_bytes()).unwrap();
+        assert_eq!(5, counters[1][Counter::Misses]);
+    }
+}